@@ -1,13 +1,15 @@
 //! Physical and virtual addresses manipulation
 
+use core::cmp::Ordering;
 use core::convert::TryFrom;
 use core::fmt;
 #[cfg(feature = "step_trait")]
 use core::iter::Step;
-use core::ops::{Add, AddAssign, Sub, SubAssign};
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Rem, Sub, SubAssign};
 
 use crate::structures::paging::page_table::PageTableLevel;
-use crate::structures::paging::{PageOffset, PageTableIndex};
+use crate::structures::paging::{PageOffset, PageSize, PageTableIndex, PageTableIndices};
 use bit_field::BitField;
 
 const ADDRESS_SPACE_SIZE: u64 = 0x1_0000_0000_0000;
@@ -99,9 +101,19 @@ impl VirtAddr {
     /// address is canonical, use [`new`](Self::new) or [`try_new`](Self::try_new).
     #[inline]
     pub const fn new_truncate(addr: u64) -> VirtAddr {
-        // By doing the right shift as a signed operation (on a i64), it will
-        // sign extend the value, repeating the leftmost bit.
-        VirtAddr(((addr << 16) as i64 >> 16) as u64)
+        VirtAddr(sign_extend(addr, 47))
+    }
+
+    /// Creates a new canonical virtual address and reports whether the given address was
+    /// already canonical.
+    ///
+    /// This behaves like [`new_truncate`](Self::new_truncate), but additionally returns `false`
+    /// if bits 48 to 64 had to be overwritten. This helps to diagnose missing sign extensions
+    /// without panicking like [`new`](Self::new).
+    #[inline]
+    pub const fn new_reporting(addr: u64) -> (VirtAddr, bool) {
+        let v = Self::new_truncate(addr);
+        (v, v.0 == addr)
     }
 
     /// Creates a new virtual address, without any checks.
@@ -115,6 +127,9 @@ impl VirtAddr {
     }
 
     /// Creates a virtual address that points to `0`.
+    ///
+    /// This is usable in const contexts, e.g. to initialize fields of statics. The
+    /// [`is_null`](Self::is_null) method checks for this address.
     #[inline]
     pub const fn zero() -> VirtAddr {
         VirtAddr(0)
@@ -153,6 +168,52 @@ impl VirtAddr {
         self.0 == 0
     }
 
+    /// Returns whether both addresses are equal.
+    ///
+    /// Unlike the `PartialEq` implementation, this method can be used in const contexts.
+    #[inline]
+    pub const fn const_eq(self, other: VirtAddr) -> bool {
+        self.0 == other.0
+    }
+
+    /// Compares this address with the given address.
+    ///
+    /// This is equivalent to the `Ord` implementation, but can be used in const contexts.
+    #[inline]
+    pub const fn const_cmp(self, other: VirtAddr) -> Ordering {
+        if self.0 < other.0 {
+            Ordering::Less
+        } else if self.0 > other.0 {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    /// Returns the lower of the two addresses.
+    ///
+    /// This is equivalent to `Ord::min`, but can be used in const contexts.
+    #[inline]
+    pub const fn min(self, other: VirtAddr) -> VirtAddr {
+        if self.0 <= other.0 {
+            self
+        } else {
+            other
+        }
+    }
+
+    /// Returns the higher of the two addresses.
+    ///
+    /// This is equivalent to `Ord::max`, but can be used in const contexts.
+    #[inline]
+    pub const fn max(self, other: VirtAddr) -> VirtAddr {
+        if self.0 >= other.0 {
+            self
+        } else {
+            other
+        }
+    }
+
     /// Aligns the virtual address upwards to the given alignment.
     ///
     /// See the `align_up` function for more information.
@@ -203,6 +264,26 @@ impl VirtAddr {
         self.align_down_u64(align).as_u64() == self.as_u64()
     }
 
+    /// Advances the virtual address by `count` pages of size `S`.
+    ///
+    /// This is equivalent to `addr + count * S::SIZE`.
+    ///
+    /// ## Panics
+    ///
+    /// This function panics if the offset overflows or if the resulting address is not
+    /// canonical.
+    #[inline]
+    pub fn add_pages<S: PageSize>(self, count: u64) -> Self {
+        let offset = count
+            .checked_mul(S::SIZE)
+            .expect("attempt to multiply with overflow");
+        VirtAddr::new(
+            self.0
+                .checked_add(offset)
+                .expect("attempt to add with overflow"),
+        )
+    }
+
     /// Returns the 12-bit page offset of this virtual address.
     #[inline]
     pub const fn page_offset(self) -> PageOffset {
@@ -233,12 +314,44 @@ impl VirtAddr {
         PageTableIndex::new_truncate((self.0 >> 12 >> 9 >> 9 >> 9) as u16)
     }
 
+    /// Returns all four page table indices and the page offset of this address at once.
+    #[inline]
+    pub const fn table_indices(self) -> PageTableIndices {
+        PageTableIndices {
+            p4: self.p4_index(),
+            p3: self.p3_index(),
+            p2: self.p2_index(),
+            p1: self.p1_index(),
+            offset: self.page_offset(),
+        }
+    }
+
     /// Returns the 9-bit level page table index.
     #[inline]
     pub const fn page_table_index(self, level: PageTableLevel) -> PageTableIndex {
         PageTableIndex::new_truncate((self.0 >> 12 >> ((level as u8 - 1) * 9)) as u16)
     }
 
+    /// Returns an iterator over the addresses `start`, `start + stride`, `start + 2 * stride`,
+    /// and so on, that are below `end`.
+    ///
+    /// Like the `Step` implementation, the iterator jumps over the non-canonical gap in the
+    /// middle of the address space, so `stride` is counted in canonical addresses and only
+    /// canonical addresses are returned. The iterator ends early if the next address would
+    /// exceed the top of the address space.
+    ///
+    /// ## Panics
+    ///
+    /// This function panics if `stride` is zero.
+    #[inline]
+    pub fn iter_step(start: Self, end: Self, stride: u64) -> impl Iterator<Item = Self> {
+        assert!(stride != 0, "stride must not be zero");
+        core::iter::successors(Some(start), move |&addr| {
+            Self::forward_checked_u64(addr, stride)
+        })
+        .take_while(move |&addr| addr < end)
+    }
+
     // FIXME: Move this into the `Step` impl, once `Step` is stabilized.
     #[cfg(feature = "step_trait")]
     pub(crate) fn steps_between_impl(start: &Self, end: &Self) -> (usize, Option<usize>) {
@@ -253,7 +366,6 @@ impl VirtAddr {
     /// An implementation of steps_between that returns u64. Note that this
     /// function always returns the exact bound, so it doesn't need to return a
     /// lower and upper bound like steps_between does.
-    #[cfg(any(feature = "instructions", feature = "step_trait"))]
     pub(crate) fn steps_between_u64(start: &Self, end: &Self) -> Option<u64> {
         let mut steps = end.0.checked_sub(start.0)?;
 
@@ -392,6 +504,52 @@ impl SubAssign<u64> for VirtAddr {
     }
 }
 
+impl Add<&u64> for VirtAddr {
+    type Output = Self;
+    #[inline]
+    fn add(self, rhs: &u64) -> Self::Output {
+        self + *rhs
+    }
+}
+
+impl AddAssign<&u64> for VirtAddr {
+    #[inline]
+    fn add_assign(&mut self, rhs: &u64) {
+        *self = *self + *rhs;
+    }
+}
+
+impl Sub<&u64> for VirtAddr {
+    type Output = Self;
+    #[inline]
+    fn sub(self, rhs: &u64) -> Self::Output {
+        self - *rhs
+    }
+}
+
+impl SubAssign<&u64> for VirtAddr {
+    #[inline]
+    fn sub_assign(&mut self, rhs: &u64) {
+        *self = *self - *rhs;
+    }
+}
+
+impl Sum<u64> for VirtAddr {
+    /// Adds up the given offsets, starting at address zero.
+    #[inline]
+    fn sum<I: Iterator<Item = u64>>(iter: I) -> Self {
+        iter.fold(VirtAddr::zero(), |addr, offset| addr + offset)
+    }
+}
+
+impl<'a> Sum<&'a u64> for VirtAddr {
+    /// Adds up the given offsets, starting at address zero.
+    #[inline]
+    fn sum<I: Iterator<Item = &'a u64>>(iter: I) -> Self {
+        iter.fold(VirtAddr::zero(), |addr, offset| addr + offset)
+    }
+}
+
 impl Sub<VirtAddr> for VirtAddr {
     type Output = u64;
     #[inline]
@@ -400,6 +558,38 @@ impl Sub<VirtAddr> for VirtAddr {
     }
 }
 
+impl Rem<u64> for VirtAddr {
+    type Output = u64;
+    #[inline]
+    fn rem(self, rhs: u64) -> Self::Output {
+        self.as_u64() % rhs
+    }
+}
+
+impl TryFrom<u64> for VirtAddr {
+    type Error = VirtAddrNotValid;
+
+    /// Tries to create a new canonical virtual address.
+    ///
+    /// See [`VirtAddr::try_new`] for more information.
+    #[inline]
+    fn try_from(addr: u64) -> Result<Self, Self::Error> {
+        Self::try_new(addr)
+    }
+}
+
+impl TryFrom<usize> for VirtAddr {
+    type Error = VirtAddrNotValid;
+
+    /// Tries to create a new canonical virtual address.
+    ///
+    /// See [`VirtAddr::try_new`] for more information.
+    #[inline]
+    fn try_from(addr: usize) -> Result<Self, Self::Error> {
+        Self::try_new(addr as u64)
+    }
+}
+
 #[cfg(feature = "step_trait")]
 impl Step for VirtAddr {
     #[inline]
@@ -478,6 +668,9 @@ impl PhysAddr {
     }
 
     /// Creates a physical address that points to `0`.
+    ///
+    /// This is usable in const contexts, e.g. to initialize fields of statics. The
+    /// [`is_null`](Self::is_null) method checks for this address.
     #[inline]
     pub const fn zero() -> PhysAddr {
         PhysAddr(0)
@@ -495,6 +688,52 @@ impl PhysAddr {
         self.0 == 0
     }
 
+    /// Returns whether both addresses are equal.
+    ///
+    /// Unlike the `PartialEq` implementation, this method can be used in const contexts.
+    #[inline]
+    pub const fn const_eq(self, other: PhysAddr) -> bool {
+        self.0 == other.0
+    }
+
+    /// Compares this address with the given address.
+    ///
+    /// This is equivalent to the `Ord` implementation, but can be used in const contexts.
+    #[inline]
+    pub const fn const_cmp(self, other: PhysAddr) -> Ordering {
+        if self.0 < other.0 {
+            Ordering::Less
+        } else if self.0 > other.0 {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    /// Returns the lower of the two addresses.
+    ///
+    /// This is equivalent to `Ord::min`, but can be used in const contexts.
+    #[inline]
+    pub const fn min(self, other: PhysAddr) -> PhysAddr {
+        if self.0 <= other.0 {
+            self
+        } else {
+            other
+        }
+    }
+
+    /// Returns the higher of the two addresses.
+    ///
+    /// This is equivalent to `Ord::max`, but can be used in const contexts.
+    #[inline]
+    pub const fn max(self, other: PhysAddr) -> PhysAddr {
+        if self.0 >= other.0 {
+            self
+        } else {
+            other
+        }
+    }
+
     /// Aligns the physical address upwards to the given alignment.
     ///
     /// See the `align_up` function for more information.
@@ -544,6 +783,44 @@ impl PhysAddr {
     pub(crate) const fn is_aligned_u64(self, align: u64) -> bool {
         self.align_down_u64(align).as_u64() == self.as_u64()
     }
+
+    /// Advances the physical address by `count` frames of size `S`.
+    ///
+    /// This is equivalent to `addr + count * S::SIZE`.
+    ///
+    /// ## Panics
+    ///
+    /// This function panics if the offset overflows or if the resulting address has a bit in
+    /// the range 52 to 64 set.
+    #[inline]
+    pub fn add_frames<S: PageSize>(self, count: u64) -> Self {
+        let offset = count
+            .checked_mul(S::SIZE)
+            .expect("attempt to multiply with overflow");
+        PhysAddr::new(
+            self.0
+                .checked_add(offset)
+                .expect("attempt to add with overflow"),
+        )
+    }
+
+    /// Returns an iterator over the addresses `start`, `start + stride`, `start + 2 * stride`,
+    /// and so on, that are below `end`.
+    ///
+    /// The iterator ends early if the next address would not be a valid physical address.
+    ///
+    /// ## Panics
+    ///
+    /// This function panics if `stride` is zero.
+    #[inline]
+    pub fn iter_step(start: Self, end: Self, stride: u64) -> impl Iterator<Item = Self> {
+        assert!(stride != 0, "stride must not be zero");
+        core::iter::successors(Some(start), move |&addr| {
+            let next = addr.0.checked_add(stride)?;
+            PhysAddr::try_new(next).ok()
+        })
+        .take_while(move |&addr| addr < end)
+    }
 }
 
 impl fmt::Debug for PhysAddr {
@@ -619,6 +896,52 @@ impl SubAssign<u64> for PhysAddr {
     }
 }
 
+impl Add<&u64> for PhysAddr {
+    type Output = Self;
+    #[inline]
+    fn add(self, rhs: &u64) -> Self::Output {
+        self + *rhs
+    }
+}
+
+impl AddAssign<&u64> for PhysAddr {
+    #[inline]
+    fn add_assign(&mut self, rhs: &u64) {
+        *self = *self + *rhs;
+    }
+}
+
+impl Sub<&u64> for PhysAddr {
+    type Output = Self;
+    #[inline]
+    fn sub(self, rhs: &u64) -> Self::Output {
+        self - *rhs
+    }
+}
+
+impl SubAssign<&u64> for PhysAddr {
+    #[inline]
+    fn sub_assign(&mut self, rhs: &u64) {
+        *self = *self - *rhs;
+    }
+}
+
+impl Sum<u64> for PhysAddr {
+    /// Adds up the given offsets, starting at address zero.
+    #[inline]
+    fn sum<I: Iterator<Item = u64>>(iter: I) -> Self {
+        iter.fold(PhysAddr::zero(), |addr, offset| addr + offset)
+    }
+}
+
+impl<'a> Sum<&'a u64> for PhysAddr {
+    /// Adds up the given offsets, starting at address zero.
+    #[inline]
+    fn sum<I: Iterator<Item = &'a u64>>(iter: I) -> Self {
+        iter.fold(PhysAddr::zero(), |addr, offset| addr + offset)
+    }
+}
+
 impl Sub<PhysAddr> for PhysAddr {
     type Output = u64;
     #[inline]
@@ -627,6 +950,83 @@ impl Sub<PhysAddr> for PhysAddr {
     }
 }
 
+impl Rem<u64> for PhysAddr {
+    type Output = u64;
+    #[inline]
+    fn rem(self, rhs: u64) -> Self::Output {
+        self.as_u64() % rhs
+    }
+}
+
+impl TryFrom<u64> for PhysAddr {
+    type Error = PhysAddrNotValid;
+
+    /// Tries to create a new physical address.
+    ///
+    /// See [`PhysAddr::try_new`] for more information.
+    #[inline]
+    fn try_from(addr: u64) -> Result<Self, Self::Error> {
+        Self::try_new(addr)
+    }
+}
+
+impl TryFrom<usize> for PhysAddr {
+    type Error = PhysAddrNotValid;
+
+    /// Tries to create a new physical address.
+    ///
+    /// See [`PhysAddr::try_new`] for more information.
+    #[inline]
+    fn try_from(addr: usize) -> Result<Self, Self::Error> {
+        Self::try_new(addr as u64)
+    }
+}
+
+/// The virtual address at which the complete physical memory is mapped.
+///
+/// Many kernels map all physical memory at a fixed offset in the virtual address space, so that
+/// physical address `0x5000` is accessible at virtual address `offset + 0x5000`. This type
+/// performs the conversions between the two address spaces for such a mapping. It describes the
+/// same relationship as the offset passed to
+/// [`OffsetPageTable::new`](crate::structures::paging::OffsetPageTable::new).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct PhysOffset(VirtAddr);
+
+impl PhysOffset {
+    /// Creates a new offset for physical memory mapped starting at `offset`.
+    #[inline]
+    pub const fn new(offset: VirtAddr) -> Self {
+        PhysOffset(offset)
+    }
+
+    /// Returns the virtual address at which physical address zero is mapped.
+    #[inline]
+    pub const fn offset(self) -> VirtAddr {
+        self.0
+    }
+
+    /// Returns the virtual address at which the given physical address is mapped.
+    ///
+    /// ## Panics
+    ///
+    /// This function panics if the resulting virtual address is not canonical.
+    #[inline]
+    pub fn to_virt(&self, phys: PhysAddr) -> VirtAddr {
+        self.0 + phys.as_u64()
+    }
+
+    /// Returns the physical address that is mapped at the given virtual address.
+    ///
+    /// Returns `None` if the virtual address lies below the offset or if the resulting physical
+    /// address is not valid.
+    #[inline]
+    pub fn to_phys(&self, virt: VirtAddr) -> Option<PhysAddr> {
+        let addr = virt.as_u64().checked_sub(self.0.as_u64())?;
+        PhysAddr::try_new(addr).ok()
+    }
+}
+
 /// Align address downwards.
 ///
 /// Returns the greatest `x` with alignment `align` so that `x <= addr`.
@@ -659,10 +1059,75 @@ pub const fn align_up(addr: u64, align: u64) -> u64 {
     }
 }
 
+/// Sign extend the given value from the given bit.
+///
+/// Returns `value` with all bits above `sign_bit` replaced by copies of bit `sign_bit`. Virtual
+/// addresses are canonical if they are sign extended from bit 47 with 4-level paging, or from
+/// bit 56 with 5-level paging.
+///
+/// Panics if `sign_bit` is not below 64.
+#[inline]
+pub const fn sign_extend(value: u64, sign_bit: u32) -> u64 {
+    assert!(sign_bit < 64, "`sign_bit` must be below 64");
+    let shift = 63 - sign_bit;
+    // By doing the right shift as a signed operation (on a i64), it will
+    // sign extend the value, repeating the leftmost bit.
+    ((value << shift) as i64 >> shift) as u64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    pub fn add_u64_ref() {
+        let offsets = [0x1000, 0x2000, 0x30];
+        let virt = offsets.iter().fold(VirtAddr::new(0x1000), |a, off| a + off);
+        assert_eq!(virt, VirtAddr::new(0x4030));
+        let phys = offsets.iter().fold(PhysAddr::new(0x1000), |a, off| a + off);
+        assert_eq!(phys, PhysAddr::new(0x4030));
+
+        let virt = offsets.iter().fold(virt, |a, off| a - off);
+        assert_eq!(virt, VirtAddr::new(0x1000));
+        let phys = offsets.iter().fold(phys, |a, off| a - off);
+        assert_eq!(phys, PhysAddr::new(0x1000));
+
+        let virt = offsets.iter().fold(virt, |mut a, off| {
+            a += off;
+            a -= off;
+            a += off;
+            a
+        });
+        assert_eq!(virt, VirtAddr::new(0x4030));
+        let phys = offsets.iter().fold(phys, |mut a, off| {
+            a += off;
+            a -= off;
+            a += off;
+            a
+        });
+        assert_eq!(phys, PhysAddr::new(0x4030));
+
+        assert_eq!(offsets.iter().sum::<VirtAddr>(), VirtAddr::new(0x3030));
+        assert_eq!(
+            offsets.iter().copied().sum::<PhysAddr>(),
+            PhysAddr::new(0x3030)
+        );
+    }
+
+    #[test]
+    pub fn phys_offset() {
+        let offset = PhysOffset::new(VirtAddr::new(0xffff_8000_0000_0000));
+        let phys = PhysAddr::new(0x5000);
+        let virt = offset.to_virt(phys);
+        assert_eq!(virt, VirtAddr::new(0xffff_8000_0000_5000));
+        assert_eq!(offset.to_phys(virt), Some(phys));
+
+        assert_eq!(offset.to_phys(VirtAddr::new(0x5000)), None);
+
+        let identity = PhysOffset::new(VirtAddr::zero());
+        assert_eq!(identity.to_phys(VirtAddr::new(0xffff_8000_0000_0000)), None);
+    }
+
     #[test]
     pub fn virtaddr_new_truncate() {
         assert_eq!(VirtAddr::new_truncate(0), VirtAddr(0));
@@ -862,6 +1327,225 @@ mod tests {
         PhysAddr::new(0x000f_ffff_ffff_ffff).align_up(2u64);
     }
 
+    #[test]
+    fn test_iter_step() {
+        let addrs: Vec<_> =
+            VirtAddr::iter_step(VirtAddr::new(0x1000), VirtAddr::new(0x1100), 0x40).collect();
+        assert_eq!(
+            addrs,
+            [0x1000, 0x1040, 0x1080, 0x10c0].map(VirtAddr::new).to_vec()
+        );
+
+        // jump over the non-canonical gap
+        let addrs: Vec<_> = VirtAddr::iter_step(
+            VirtAddr::new(0x7fff_ffff_e000),
+            VirtAddr::new(0xffff_8000_0000_2000),
+            0x1000,
+        )
+        .collect();
+        assert_eq!(
+            addrs,
+            [
+                0x7fff_ffff_e000,
+                0x7fff_ffff_f000,
+                0xffff_8000_0000_0000,
+                0xffff_8000_0000_1000
+            ]
+            .map(VirtAddr::new)
+            .to_vec()
+        );
+
+        // stop at the top of the address space
+        let top = VirtAddr::new(0xffff_ffff_ffff_f000);
+        let addrs: Vec<_> = VirtAddr::iter_step(top, VirtAddr::new(u64::MAX), 0x1000).collect();
+        assert_eq!(addrs, vec![top]);
+
+        // empty range
+        assert_eq!(
+            VirtAddr::iter_step(VirtAddr::new(0x2000), VirtAddr::new(0x1000), 1).count(),
+            0
+        );
+
+        let addrs: Vec<_> =
+            PhysAddr::iter_step(PhysAddr::new(0), PhysAddr::new(0x3001), 0x1000).collect();
+        assert_eq!(
+            addrs,
+            [0, 0x1000, 0x2000, 0x3000].map(PhysAddr::new).to_vec()
+        );
+
+        let top = PhysAddr::new(0x000f_ffff_ffff_f000);
+        let addrs: Vec<_> =
+            PhysAddr::iter_step(top, PhysAddr::new(0x000f_ffff_ffff_ffff), 0x1000).collect();
+        assert_eq!(addrs, vec![top]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_iter_step_zero_stride() {
+        let _ = VirtAddr::iter_step(VirtAddr::new(0), VirtAddr::new(0x1000), 0);
+    }
+
+    #[test]
+    fn test_rem() {
+        assert_eq!(VirtAddr::new(0x1234) % 0x1000, 0x234);
+        assert_eq!(VirtAddr::new(0xffff_8000_0000_1000) % 0x1000, 0);
+        assert_eq!(PhysAddr::new(0x1234) % 0x1000, 0x234);
+        assert_eq!(PhysAddr::new(0x20_0000) % 0x20_0000, 0);
+    }
+
+    #[test]
+    fn test_try_from() {
+        assert_eq!(
+            VirtAddr::try_from(0xffff_8000_0000_0000u64).ok(),
+            Some(VirtAddr(0xffff_8000_0000_0000))
+        );
+        assert!(VirtAddr::try_from(0x8000_0000_0000u64).is_err());
+        assert_eq!(VirtAddr::try_from(0x1000usize).ok(), Some(VirtAddr(0x1000)));
+
+        assert_eq!(
+            PhysAddr::try_from(0x000f_ffff_ffff_ffffu64).ok(),
+            Some(PhysAddr(0x000f_ffff_ffff_ffff))
+        );
+        assert!(PhysAddr::try_from(0x0010_0000_0000_0000u64).is_err());
+        assert_eq!(PhysAddr::try_from(0x1000usize).ok(), Some(PhysAddr(0x1000)));
+    }
+
+    #[test]
+    fn test_new_reporting() {
+        assert_eq!(
+            VirtAddr::new_reporting(0x1000),
+            (VirtAddr::new(0x1000), true)
+        );
+        assert_eq!(
+            VirtAddr::new_reporting(0xffff_8000_0000_0000),
+            (VirtAddr::new(0xffff_8000_0000_0000), true)
+        );
+        assert_eq!(
+            VirtAddr::new_reporting(0x8000_0000_0000),
+            (VirtAddr::new(0xffff_8000_0000_0000), false)
+        );
+        assert_eq!(
+            VirtAddr::new_reporting(0x1234_0000_0000_1000),
+            (VirtAddr::new(0x1000), false)
+        );
+    }
+
+    #[test]
+    fn test_add_pages() {
+        use crate::structures::paging::{Size1GiB, Size2MiB, Size4KiB};
+
+        let addr = VirtAddr::new(0x1000);
+        assert_eq!(addr.add_pages::<Size4KiB>(2), VirtAddr::new(0x3000));
+        assert_eq!(addr.add_pages::<Size2MiB>(1), VirtAddr::new(0x20_1000));
+        assert_eq!(addr.add_pages::<Size1GiB>(0), addr);
+
+        let addr = PhysAddr::new(0x1000);
+        assert_eq!(addr.add_frames::<Size4KiB>(3), PhysAddr::new(0x4000));
+        assert_eq!(addr.add_frames::<Size1GiB>(1), PhysAddr::new(0x4000_1000));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_pages_non_canonical() {
+        use crate::structures::paging::Size4KiB;
+
+        VirtAddr::new(0x7fff_ffff_f000).add_pages::<Size4KiB>(1);
+    }
+
+    #[test]
+    fn test_zero() {
+        const VIRT: VirtAddr = VirtAddr::zero();
+        const PHYS: PhysAddr = PhysAddr::zero();
+        assert!(VIRT.is_null());
+        assert!(PHYS.is_null());
+        assert_eq!(VIRT, VirtAddr::new(0));
+        assert_eq!(PHYS, PhysAddr::new(0));
+        assert!(!VirtAddr::new(0x1000).is_null());
+        assert!(!PhysAddr::new(0x1000).is_null());
+    }
+
+    #[test]
+    fn test_const_comparison() {
+        const LOW: VirtAddr = VirtAddr::new(0x1000);
+        const HIGH: VirtAddr = VirtAddr::new(0xffff_8000_0000_0000);
+        const _: () = assert!(LOW.min(HIGH).const_eq(LOW));
+        const _: () = assert!(LOW.max(HIGH).const_eq(HIGH));
+        const _: () = assert!(matches!(LOW.const_cmp(HIGH), Ordering::Less));
+        assert_eq!(LOW.const_cmp(HIGH), LOW.cmp(&HIGH));
+        assert_eq!(HIGH.const_cmp(HIGH), Ordering::Equal);
+
+        const A: PhysAddr = PhysAddr::new(0x2000);
+        const B: PhysAddr = PhysAddr::new(0x3000);
+        const _: () = assert!(A.max(B).const_eq(B));
+        const _: () = assert!(!A.const_eq(B));
+        assert_eq!(B.const_cmp(A), Ordering::Greater);
+        assert_eq!(A.min(B), A);
+    }
+
+    #[test]
+    fn test_sign_extend() {
+        // 4-level paging
+        assert_eq!(
+            sign_extend(0x0000_7fff_ffff_ffff, 47),
+            0x0000_7fff_ffff_ffff
+        );
+        assert_eq!(
+            sign_extend(0x0000_8000_0000_0000, 47),
+            0xffff_8000_0000_0000
+        );
+        assert_eq!(
+            sign_extend(0x1234_8000_0000_0000, 47),
+            0xffff_8000_0000_0000
+        );
+        assert_eq!(
+            sign_extend(0xffff_7fff_ffff_ffff, 47),
+            0x0000_7fff_ffff_ffff
+        );
+
+        // 5-level paging
+        assert_eq!(
+            sign_extend(0x00ff_ffff_ffff_ffff, 56),
+            0x00ff_ffff_ffff_ffff
+        );
+        assert_eq!(
+            sign_extend(0x01ff_0000_0000_0000, 56),
+            0xffff_0000_0000_0000
+        );
+        assert_eq!(
+            sign_extend(0x0100_0000_0000_0000, 56),
+            0xff00_0000_0000_0000
+        );
+        assert_eq!(sign_extend(0xfe00_0000_0000_0000, 56), 0);
+
+        assert_eq!(
+            sign_extend(0x8000_0000_0000_0000, 63),
+            0x8000_0000_0000_0000
+        );
+        assert_eq!(sign_extend(0x1, 0), u64::MAX);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_pointer_format() {
+        assert_eq!(
+            format!("{:p}", VirtAddr::new(0xffff_8000_0000_1000)),
+            "0xffff800000001000"
+        );
+        assert_eq!(format!("{:p}", PhysAddr::new(0x1000)), "0x1000");
+        assert_eq!(format!("{:p}", VirtAddr::zero()), "0x0");
+    }
+
+    #[test]
+    fn test_table_indices() {
+        let addr = VirtAddr::new((1 << 39) | (2 << 30) | (3 << 21) | (4 << 12) | 5);
+        let indices = addr.table_indices();
+        assert_eq!(indices.p4, PageTableIndex::new(1));
+        assert_eq!(indices.p3, PageTableIndex::new(2));
+        assert_eq!(indices.p2, PageTableIndex::new(3));
+        assert_eq!(indices.p1, PageTableIndex::new(4));
+        assert_eq!(indices.offset, PageOffset::new(5));
+    }
+
     #[test]
     #[cfg(target_pointer_width = "64")]
     fn test_from_ptr_array() {
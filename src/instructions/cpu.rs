@@ -0,0 +1,91 @@
+//! Queries for processor properties reported by the `cpuid` instruction.
+
+use crate::structures::paging::{PageSize, Size1GiB, Size2MiB};
+
+/// Returns the number of physical address bits supported by the CPU (`MAXPHYADDR`).
+///
+/// Physical addresses, and thus the address bits of page table entries, must not have bits
+/// set at or above this width. [`PhysAddr`](crate::PhysAddr) only enforces the architectural
+/// maximum of 52 bits, so this can be used to validate addresses on CPUs supporting fewer.
+///
+/// Falls back to 36 bits if the CPU doesn't report the width, as specified by the Intel SDM.
+#[inline]
+pub fn physical_address_bits() -> u8 {
+    // The width is reported in CPUID page 8000_0008h, eax bits 0..8
+    let max_extended_leaf = unsafe { core::arch::x86_64::__cpuid(0x8000_0000) }.eax;
+    if max_extended_leaf >= 0x8000_0008 {
+        unsafe { core::arch::x86_64::__cpuid(0x8000_0008) }.eax as u8
+    } else {
+        36
+    }
+}
+
+/// Returns the size of a cache line in bytes, as used by the `clflush` instruction.
+#[inline]
+pub fn cache_line_size() -> u16 {
+    // The size is reported in 8-byte units in CPUID page 01h, ebx bits 8..16
+    let cpuid = unsafe { core::arch::x86_64::__cpuid(0x1) };
+    ((cpuid.ebx >> 8) & 0xff) as u16 * 8
+}
+
+/// Returns whether the CPU supports 1GiB pages, i.e. the `HUGE_PAGE` flag in level 3 entries.
+///
+/// Support is reported by the `Page1GB` bit in CPUID page 8000_0001h. Mapping a 1GiB page on
+/// a CPU without support causes a page fault with the
+/// [`MALFORMED_TABLE`](crate::structures::idt::PageFaultErrorCode::MALFORMED_TABLE) flag on
+/// access.
+#[inline]
+pub fn supports_1gib_pages() -> bool {
+    // The flag is reported in CPUID page 8000_0001h, edx bit 26
+    let max_extended_leaf = unsafe { core::arch::x86_64::__cpuid(0x8000_0000) }.eax;
+    max_extended_leaf >= 0x8000_0001
+        && unsafe { core::arch::x86_64::__cpuid(0x8000_0001) }.edx & (1 << 26) != 0
+}
+
+/// Returns the size in bytes of the largest page size supported by the CPU.
+///
+/// 2MiB pages are always supported in long mode, so this returns either
+/// [`Size1GiB::SIZE`] or [`Size2MiB::SIZE`].
+#[inline]
+pub fn max_page_size() -> u64 {
+    if supports_1gib_pages() {
+        Size1GiB::SIZE
+    } else {
+        Size2MiB::SIZE
+    }
+}
+
+/// Returns whether the CPU supports the non-serializing `wrmsrns` instruction, which is used by
+/// [`Msr::write_nonserializing`](crate::registers::model_specific::Msr::write_nonserializing).
+#[inline]
+pub fn supports_wrmsrns() -> bool {
+    // The flag is reported in CPUID page 07h, subleaf 1, eax bit 19. Subleaf 0 reports the
+    // highest supported subleaf in eax.
+    let max_leaf = unsafe { core::arch::x86_64::__cpuid(0x0) }.eax;
+    if max_leaf < 0x7 {
+        return false;
+    }
+    let max_subleaf = unsafe { core::arch::x86_64::__cpuid_count(0x7, 0x0) }.eax;
+    max_subleaf >= 0x1
+        && unsafe { core::arch::x86_64::__cpuid_count(0x7, 0x1) }.eax & (1 << 19) != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_cpu_queries() {
+        let bits = physical_address_bits();
+        assert!((32..=52).contains(&bits));
+
+        let size = cache_line_size();
+        assert!(size == 0 || size.is_power_of_two());
+
+        let page_size = max_page_size();
+        assert_eq!(page_size == Size1GiB::SIZE, supports_1gib_pages());
+
+        // Only checks that querying the flag doesn't fault.
+        let _ = supports_wrmsrns();
+    }
+}
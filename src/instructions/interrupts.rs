@@ -3,6 +3,11 @@
 use core::arch::asm;
 
 /// Returns whether interrupts are enabled.
+///
+/// This reads the [`INTERRUPT_FLAG`](crate::registers::rflags::RFlags::INTERRUPT_FLAG)
+/// of the `RFLAGS` register, so the result only reflects the state of the
+/// current CPU. Other CPUs might have interrupts enabled or disabled
+/// independently.
 #[inline]
 pub fn are_enabled() -> bool {
     use crate::registers::rflags::{self, RFlags};
@@ -34,6 +39,45 @@ pub fn disable() {
     }
 }
 
+/// Disable interrupts and return whether they were enabled before.
+///
+/// The `RFLAGS` register is read and interrupts are disabled within a single `asm` block, so
+/// no interrupt can occur in between. Pass the returned value to [`restore`] to return to the
+/// previous state, e.g. at the end of a critical section:
+///
+/// ```ignore
+/// let were_enabled = disable_save();
+/// // interrupts are disabled
+/// restore(were_enabled);
+/// // interrupts are enabled again if they were enabled before
+/// ```
+///
+/// For the common case of running a closure, [`without_interrupts`] does this automatically.
+#[inline]
+pub fn disable_save() -> bool {
+    use crate::registers::rflags::RFlags;
+
+    let flags: u64;
+    // Omit `nomem` to imitate a lock acquire. Otherwise, the compiler
+    // is free to move reads and writes through this asm block.
+    unsafe {
+        asm!("pushfq", "cli", "pop {}", out(reg) flags, options(preserves_flags));
+    }
+    flags & RFlags::INTERRUPT_FLAG.bits() != 0
+}
+
+/// Enable interrupts if `enabled` is `true` and disable them otherwise.
+///
+/// This is the counterpart of [`disable_save`].
+#[inline]
+pub fn restore(enabled: bool) {
+    if enabled {
+        enable();
+    } else {
+        disable();
+    }
+}
+
 /// Run a closure with disabled interrupts.
 ///
 /// Run the given closure, disabling interrupts before running it (if they aren't already disabled).
@@ -59,13 +103,8 @@ pub fn without_interrupts<F, R>(f: F) -> R
 where
     F: FnOnce() -> R,
 {
-    // true if the interrupt flag is set (i.e. interrupts are enabled)
-    let saved_intpt_flag = are_enabled();
-
-    // if interrupts are enabled, disable them for now
-    if saved_intpt_flag {
-        disable();
-    }
+    // true if the interrupt flag was set (i.e. interrupts were enabled)
+    let saved_intpt_flag = disable_save();
 
     // do `f` while interrupts are disabled
     let ret = f();
@@ -108,6 +147,14 @@ where
 ///
 /// ```
 ///
+/// Note that calling [`enable`] followed by [`hlt`](crate::instructions::hlt)
+/// is _not_ equivalent to this function. The two calls are separate `asm`
+/// blocks, so the compiler is free to place other instructions between the
+/// `sti` and the `hlt`. The interrupt shadow then ends before the `hlt` is
+/// executed, which reintroduces the race described above. This function emits
+/// both instructions in a single `asm` block to guarantee that they are
+/// executed back to back.
+///
 /// ## Non-maskable Interrupts
 ///
 /// On some processors, the interrupt shadow of `sti` does not apply to
@@ -137,17 +184,145 @@ pub fn int3() {
     }
 }
 
+/// Triggers a breakpoint that is caught by an attached debugger or the `#BP` handler.
+///
+/// This emits the one-byte `int3` instruction, which raises a breakpoint exception (vector 3)
+/// and is what debuggers use for software breakpoints. Execution continues after the
+/// instruction if the handler returns. If no handler is installed in the IDT, the exception
+/// escalates to a double fault.
+///
+/// Unlike [`bochs_breakpoint`](crate::instructions::bochs_breakpoint), which is only
+/// recognized by the Bochs emulator and is a no-op everywhere else, this always causes an
+/// exception.
+#[inline]
+pub fn breakpoint() {
+    int3();
+}
+
+/// Returns whether `int` must not be used with the given vector, either because the CPU pushes
+/// an error code for the exception with this vector or because the vector is reserved.
+#[cfg(feature = "asm_const")]
+const fn is_invalid_software_interrupt(vector: u8) -> bool {
+    matches!(
+        vector,
+        // exceptions with error code
+        0x08 | 0x0A..=0x0E | 0x11 | 0x15 | 0x1D | 0x1E
+        // reserved
+        | 0x09 | 0x0F | 0x16..=0x1B | 0x1F
+    )
+}
+
+#[cfg(feature = "asm_const")]
+struct SoftwareInterrupt<const NUM: u8>;
+
+#[cfg(feature = "asm_const")]
+impl<const NUM: u8> SoftwareInterrupt<NUM> {
+    const VALID: () = assert!(
+        !is_invalid_software_interrupt(NUM),
+        "`int` must not be used with exception vectors that push an error code or with reserved vectors"
+    );
+}
+
 /// Generate a software interrupt by invoking the `int` instruction.
 ///
+/// The `int` instruction never pushes an error code. Invoking the vector of an exception that
+/// has an error code, e.g. a page fault (#14), would thus call a handler that expects an error
+/// code on the stack without one, which corrupts the interrupt stack frame. For this reason, it
+/// is a compile-time error to use this function with such a vector, or with a reserved vector:
+///
+/// ```compile_fail
+/// unsafe { x86_64::instructions::interrupts::software_interrupt::<14>() };
+/// ```
+///
 /// ## Safety
 ///
 /// Invoking an arbitrary interrupt is unsafe. It can cause your system to
-/// crash if you invoke a double-fault (#8) or machine-check (#18) exception.
+/// crash if you invoke a machine-check (#18) exception.
 /// It can also cause memory/register corruption depending on the interrupt
 /// implementation (if it expects values/pointers to be passed in registers).
 #[cfg(feature = "asm_const")]
 pub unsafe fn software_interrupt<const NUM: u8>() {
+    #[allow(clippy::let_unit_value)]
+    let () = SoftwareInterrupt::<NUM>::VALID;
     unsafe {
         asm!("int {num}", num = const NUM, options(nomem, nostack));
     }
 }
+
+/// Masking of non-maskable interrupts (NMIs) through the legacy CMOS latch.
+///
+/// NMIs can't be masked by [`disable`]. On PC-compatible systems, bit 7 of
+/// the CMOS index register (I/O port `0x70`) controls a latch that gates the
+/// NMI line of the legacy chipset instead. This is
+/// commonly used when reprogramming the RTC or CMOS to avoid an NMI between
+/// selecting a CMOS register and accessing it.
+///
+/// Note that this only affects the legacy NMI latch. NMIs from other sources,
+/// e.g. the local APIC (`LINT1` or IPIs with NMI delivery mode) or the
+/// watchdog, are not masked by these functions.
+pub mod nmi {
+    use crate::instructions::port::Port;
+
+    const CMOS_INDEX: u16 = 0x70;
+    const NMI_DISABLE: u8 = 1 << 7;
+
+    /// Enable NMIs by clearing the NMI-disable bit of the CMOS index register.
+    ///
+    /// The CMOS index register is write-only on many chipsets, so the rest of its value can't
+    /// be preserved. Instead, `cmos_index` is written to the register together with the
+    /// cleared NMI-disable bit. It should be the CMOS register that is accessed next, if any.
+    ///
+    /// ## Safety
+    ///
+    /// This function is unsafe because it writes the CMOS index register. The
+    /// caller must ensure that this does not interfere with a concurrent
+    /// access to the CMOS, e.g. from another CPU or an interrupt handler.
+    #[inline]
+    pub unsafe fn enable(cmos_index: u8) {
+        unsafe { write_index(cmos_index & !NMI_DISABLE) }
+    }
+
+    /// Disable NMIs by setting the NMI-disable bit of the CMOS index register.
+    ///
+    /// Like for [`enable`], `cmos_index` is written to the register together with the
+    /// NMI-disable bit.
+    ///
+    /// ## Safety
+    ///
+    /// This function is unsafe because it writes the CMOS index register. The
+    /// caller must ensure that this does not interfere with a concurrent
+    /// access to the CMOS, e.g. from another CPU or an interrupt handler.
+    #[inline]
+    pub unsafe fn disable(cmos_index: u8) {
+        unsafe { write_index(cmos_index | NMI_DISABLE) }
+    }
+
+    /// Run a closure with the legacy NMI latch disabled.
+    ///
+    /// NMIs are disabled before running the closure and enabled again
+    /// afterwards, both times selecting the CMOS register `cmos_index`. Since
+    /// the state of the latch can't be read back reliably on all chipsets,
+    /// NMIs are always enabled after the closure returns, even if they were
+    /// disabled before.
+    ///
+    /// ## Safety
+    ///
+    /// See [`enable`] and [`disable`].
+    #[inline]
+    pub unsafe fn without_nmi<F, R>(cmos_index: u8, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        unsafe { disable(cmos_index) };
+        let ret = f();
+        unsafe { enable(cmos_index) };
+        ret
+    }
+
+    /// Writes the CMOS index register without accessing the data port, since reading a
+    /// register can have side effects, e.g. reading register `0x0C` acknowledges RTC interrupts.
+    unsafe fn write_index(value: u8) {
+        let mut index: Port<u8> = Port::new(CMOS_INDEX);
+        unsafe { index.write(value) }
+    }
+}
@@ -0,0 +1,82 @@
+//! Fast operations on whole 4KiB pages using string instructions.
+
+use crate::{
+    structures::paging::{PageSize, Size4KiB},
+    VirtAddr,
+};
+use core::arch::asm;
+
+/// The number of quadwords in a 4KiB page.
+const QUADS_PER_PAGE: u64 = Size4KiB::SIZE / 8;
+
+/// Fills the 4KiB page starting at `dst` with zeros using `rep stosq`.
+///
+/// This is useful for clearing freshly allocated frames, e.g. before using them as page tables.
+///
+/// ## Safety
+///
+/// The caller must ensure that `dst` is page-aligned and that the complete page is mapped as
+/// writable. No references to the page may be alive during the call.
+#[inline]
+pub unsafe fn zero_page(dst: VirtAddr) {
+    debug_assert!(dst.is_aligned(Size4KiB::SIZE));
+    unsafe {
+        asm!(
+            "rep stosq",
+            inout("rcx") QUADS_PER_PAGE => _,
+            inout("rdi") dst.as_u64() => _,
+            in("rax") 0u64,
+            options(nostack, preserves_flags),
+        );
+    }
+}
+
+/// Copies the 4KiB page starting at `src` to the page starting at `dst` using `rep movsq`.
+///
+/// ## Safety
+///
+/// The caller must ensure that both `dst` and `src` are page-aligned, that both pages are
+/// mapped, and that `dst` is writable. The two pages must not overlap, i.e. `dst` must not be
+/// equal to `src`. No mutable references to `src` and no references to `dst` may be alive
+/// during the call.
+#[inline]
+pub unsafe fn copy_page(dst: VirtAddr, src: VirtAddr) {
+    debug_assert!(dst.is_aligned(Size4KiB::SIZE));
+    debug_assert!(src.is_aligned(Size4KiB::SIZE));
+    debug_assert_ne!(dst, src);
+    unsafe {
+        asm!(
+            "rep movsq",
+            inout("rcx") QUADS_PER_PAGE => _,
+            inout("rdi") dst.as_u64() => _,
+            inout("rsi") src.as_u64() => _,
+            options(nostack, preserves_flags),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ptr;
+
+    #[repr(C, align(4096))]
+    struct AlignedPage([u8; 4096]);
+
+    #[test]
+    fn zero_and_copy() {
+        let mut src = AlignedPage([0xAB; 4096]);
+        let mut dst = AlignedPage([0; 4096]);
+
+        unsafe {
+            copy_page(
+                VirtAddr::from_ptr(ptr::addr_of_mut!(dst)),
+                VirtAddr::from_ptr(&src),
+            )
+        };
+        assert!(dst.0.iter().all(|&b| b == 0xAB));
+
+        unsafe { zero_page(VirtAddr::from_ptr(ptr::addr_of_mut!(src))) };
+        assert!(src.0.iter().all(|&b| b == 0));
+    }
+}
@@ -2,10 +2,14 @@
 
 //! Special x86_64 instructions.
 
+pub mod cpu;
 pub mod interrupts;
+pub mod mem;
+pub mod pic8259;
 pub mod port;
 pub mod random;
 pub mod segmentation;
+pub mod serial;
 pub mod tables;
 pub mod tlb;
 
@@ -44,6 +48,18 @@ pub fn bochs_breakpoint() {
     }
 }
 
+/// Causes an invalid opcode exception (`#UD`) by executing the `ud2` instruction.
+///
+/// This is useful for testing the invalid opcode handler or for marking code that should fault
+/// instead of continuing. The instruction never returns: if the exception handler returns, the
+/// `ud2` instruction is executed again.
+#[inline]
+pub fn ud2() -> ! {
+    unsafe {
+        asm!("ud2", options(nomem, nostack, noreturn));
+    }
+}
+
 /// Gets the current instruction pointer. Note that this is only approximate as it requires a few
 /// instructions to execute.
 #[inline(always)]
@@ -54,3 +70,105 @@ pub fn read_rip() -> crate::VirtAddr {
     }
     crate::VirtAddr::new(rip)
 }
+
+/// Reads the processor ID that the kernel stored in the `IA32_TSC_AUX` MSR.
+///
+/// Uses the `rdpid` instruction if it is supported and falls back to `rdtscp` otherwise, which
+/// returns the same value as a side effect of reading the time-stamp counter. The supported
+/// instruction is detected through `cpuid` on the first call.
+///
+/// The returned value is whatever the kernel wrote to `IA32_TSC_AUX` (MSR `0xC000_0103`) on
+/// the current CPU, conventionally the CPU index. If the kernel didn't initialize the MSR on
+/// every CPU, the result is meaningless. Note that the value only identifies the CPU the code
+/// was running on at the time of the call, the thread might be migrated right afterwards.
+///
+/// ## Panics
+///
+/// Panics if the CPU supports neither `rdpid` nor `rdtscp`.
+#[inline]
+pub fn read_pid() -> u32 {
+    use core::sync::atomic::{AtomicU8, Ordering};
+
+    const UNKNOWN: u8 = 0;
+    const RDPID: u8 = 1;
+    const RDTSCP: u8 = 2;
+    const UNSUPPORTED: u8 = 3;
+
+    static METHOD: AtomicU8 = AtomicU8::new(UNKNOWN);
+
+    let mut method = METHOD.load(Ordering::Relaxed);
+    if method == UNKNOWN {
+        // RDPID support is indicated by CPUID page 07h, ecx bit 22, RDTSCP support by
+        // CPUID page 8000_0001h, edx bit 27.
+        let max_leaf = unsafe { core::arch::x86_64::__cpuid(0) }.eax;
+        let max_extended_leaf = unsafe { core::arch::x86_64::__cpuid(0x8000_0000) }.eax;
+        method = if max_leaf >= 7
+            && unsafe { core::arch::x86_64::__cpuid_count(7, 0) }.ecx & (1 << 22) != 0
+        {
+            RDPID
+        } else if max_extended_leaf >= 0x8000_0001
+            && unsafe { core::arch::x86_64::__cpuid(0x8000_0001) }.edx & (1 << 27) != 0
+        {
+            RDTSCP
+        } else {
+            UNSUPPORTED
+        };
+        METHOD.store(method, Ordering::Relaxed);
+    }
+
+    match method {
+        RDPID => {
+            let pid: u64;
+            unsafe {
+                asm!("rdpid {}", out(reg) pid, options(nomem, nostack, preserves_flags));
+            }
+            pid as u32
+        }
+        RDTSCP => {
+            let pid: u32;
+            unsafe {
+                asm!(
+                    "rdtscp",
+                    out("eax") _,
+                    out("edx") _,
+                    out("ecx") pid,
+                    options(nomem, nostack, preserves_flags),
+                );
+            }
+            pid
+        }
+        _ => panic!("neither rdpid nor rdtscp is supported"),
+    }
+}
+
+/// Asserts that the given [`Cr4Flags`](crate::registers::control::Cr4Flags) are set, in debug
+/// builds only.
+///
+/// Some instructions require a feature to be enabled in `CR4` and cause an exception (e.g.
+/// `#UD`) otherwise. Wrappers for these instructions call this function first, which turns the
+/// exception into a panic with a clear message during development. In release builds (i.e.
+/// without `debug_assertions`), this function does nothing.
+///
+/// `CR4` can only be read in ring 0, so the check is skipped when running in another privilege
+/// level.
+#[inline]
+#[track_caller]
+pub fn assert_cr4(flags: crate::registers::control::Cr4Flags) {
+    #[cfg(debug_assertions)]
+    {
+        use crate::registers::control::Cr4;
+        use crate::registers::segmentation::{Segment, CS};
+        use crate::PrivilegeLevel;
+
+        if CS::get_reg().rpl() == PrivilegeLevel::Ring0 {
+            let missing = flags - Cr4::read();
+            assert!(
+                missing.is_empty(),
+                "required CR4 flags are not enabled: {:?}",
+                missing
+            );
+        }
+    }
+    #[cfg(not(debug_assertions))]
+    let _ = flags;
+}
@@ -0,0 +1,238 @@
+//! Support for the legacy pair of chained 8259 Programmable Interrupt Controllers.
+//!
+//! The PICs deliver the IRQs 0..16 of the legacy ISA bus. After reset, the master PIC delivers
+//! its IRQs on the vectors 8..16, which conflicts with the CPU exceptions. The [`Pic8259`] type
+//! remaps both controllers to free vectors, masks or unmasks individual IRQ lines, and signals
+//! the end of an interrupt.
+
+use crate::instructions::port::Port;
+
+/// Command sent to begin the initialization sequence (ICW1), indicating that ICW4 follows.
+const CMD_INIT: u8 = 0x11;
+
+/// Command sent to acknowledge an interrupt.
+const CMD_END_OF_INTERRUPT: u8 = 0x20;
+
+/// ICW4 value selecting 8086/88 mode.
+const MODE_8086: u8 = 0x01;
+
+/// The IRQ line of the master PIC that the slave PIC is connected to.
+const CASCADE_IRQ: u8 = 2;
+
+/// A single 8259 controller.
+#[derive(Debug)]
+struct Pic {
+    /// The first vector that this PIC delivers its IRQs on.
+    offset: u8,
+    command: Port<u8>,
+    data: Port<u8>,
+}
+
+impl Pic {
+    const fn new(offset: u8, command: u16, data: u16) -> Self {
+        Pic {
+            offset,
+            command: Port::new(command),
+            data: Port::new(data),
+        }
+    }
+
+    /// Returns whether this PIC delivers the given interrupt vector.
+    const fn handles_interrupt(&self, vector: u8) -> bool {
+        self.offset <= vector && vector < self.offset.wrapping_add(8)
+    }
+
+    unsafe fn end_of_interrupt(&mut self) {
+        unsafe { self.command.write(CMD_END_OF_INTERRUPT) }
+    }
+}
+
+/// The master and slave 8259 PICs, chained together as in the IBM PC/AT.
+///
+/// IRQs 0..8 are delivered by the master PIC on the vectors `offset1..offset1 + 8` and IRQs
+/// 8..16 by the slave PIC on the vectors `offset2..offset2 + 8`.
+///
+/// ## Example
+///
+/// ```no_run
+/// use x86_64::instructions::pic8259::Pic8259;
+///
+/// let mut pics = Pic8259::new(32, 40);
+/// unsafe {
+///     pics.initialize();
+///     // only deliver the timer and keyboard interrupts
+///     pics.write_masks(0xff, 0xff);
+///     pics.unmask(0);
+///     pics.unmask(1);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Pic8259 {
+    master: Pic,
+    slave: Pic,
+}
+
+impl Pic8259 {
+    /// Creates a new instance for the PICs at the standard I/O ports, remapped to the given
+    /// vector offsets once [`initialize`](Self::initialize) is called.
+    #[inline]
+    pub const fn new(offset1: u8, offset2: u8) -> Self {
+        Pic8259 {
+            master: Pic::new(offset1, 0x20, 0x21),
+            slave: Pic::new(offset2, 0xA0, 0xA1),
+        }
+    }
+
+    /// Runs the initialization sequence that remaps both PICs to the vector offsets passed to
+    /// [`new`](Self::new). The IRQ masks are preserved.
+    ///
+    /// ## Safety
+    ///
+    /// This function is unsafe because the caller must ensure that the PICs are present and
+    /// that the chosen vectors don't overlap with the CPU exceptions or other interrupt sources.
+    /// Interrupts should be disabled while the PICs are reprogrammed.
+    pub unsafe fn initialize(&mut self) {
+        // Writing to the unused port 0x80 takes long enough for the PICs to process the
+        // previous command on older hardware.
+        let mut wait_port: Port<u8> = Port::new(0x80);
+        let mut wait = || unsafe { wait_port.write(0) };
+
+        unsafe {
+            let (mask1, mask2) = self.read_masks();
+
+            self.master.command.write(CMD_INIT);
+            wait();
+            self.slave.command.write(CMD_INIT);
+            wait();
+
+            self.master.data.write(self.master.offset);
+            wait();
+            self.slave.data.write(self.slave.offset);
+            wait();
+
+            self.master.data.write(1 << CASCADE_IRQ);
+            wait();
+            self.slave.data.write(CASCADE_IRQ);
+            wait();
+
+            self.master.data.write(MODE_8086);
+            wait();
+            self.slave.data.write(MODE_8086);
+            wait();
+
+            self.write_masks(mask1, mask2);
+        }
+    }
+
+    /// Reads the interrupt masks of the master and the slave PIC.
+    ///
+    /// A set bit means that the corresponding IRQ line is masked.
+    ///
+    /// ## Safety
+    ///
+    /// This function is unsafe because the caller must ensure that the PICs are present.
+    #[inline]
+    pub unsafe fn read_masks(&mut self) -> (u8, u8) {
+        unsafe { (self.master.data.read(), self.slave.data.read()) }
+    }
+
+    /// Writes the interrupt masks of the master and the slave PIC.
+    ///
+    /// A set bit masks the corresponding IRQ line.
+    ///
+    /// ## Safety
+    ///
+    /// This function is unsafe because the caller must ensure that the PICs are present.
+    #[inline]
+    pub unsafe fn write_masks(&mut self, mask1: u8, mask2: u8) {
+        unsafe {
+            self.master.data.write(mask1);
+            self.slave.data.write(mask2);
+        }
+    }
+
+    /// Masks all IRQ lines of both PICs, e.g. before switching to the APIC.
+    ///
+    /// ## Safety
+    ///
+    /// This function is unsafe because the caller must ensure that the PICs are present.
+    #[inline]
+    pub unsafe fn disable(&mut self) {
+        unsafe { self.write_masks(0xff, 0xff) }
+    }
+
+    /// Masks the given IRQ line (0..16).
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `irq` is not below 16.
+    ///
+    /// ## Safety
+    ///
+    /// This function is unsafe because the caller must ensure that the PICs are present.
+    #[inline]
+    pub unsafe fn mask(&mut self, irq: u8) {
+        let data = self.data_port(irq);
+        unsafe {
+            let mask = data.read();
+            data.write(mask | (1 << (irq % 8)));
+        }
+    }
+
+    /// Unmasks the given IRQ line (0..16).
+    ///
+    /// Note that IRQs 8..16 are only delivered if the cascade line (IRQ 2) is unmasked too.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `irq` is not below 16.
+    ///
+    /// ## Safety
+    ///
+    /// This function is unsafe because the caller must ensure that the PICs are present and
+    /// that a handler is installed for the corresponding interrupt vector.
+    #[inline]
+    pub unsafe fn unmask(&mut self, irq: u8) {
+        let data = self.data_port(irq);
+        unsafe {
+            let mask = data.read();
+            data.write(mask & !(1 << (irq % 8)));
+        }
+    }
+
+    /// Returns whether the given interrupt vector is delivered by one of the PICs.
+    #[inline]
+    pub const fn handles_interrupt(&self, vector: u8) -> bool {
+        self.master.handles_interrupt(vector) || self.slave.handles_interrupt(vector)
+    }
+
+    /// Signals the end of the given interrupt to the PICs that delivered it.
+    ///
+    /// Interrupts delivered by the slave PIC must be acknowledged on both PICs. Vectors that are
+    /// not delivered by the PICs are ignored.
+    ///
+    /// ## Safety
+    ///
+    /// This function is unsafe because the caller must ensure that the interrupt was actually
+    /// delivered by the PICs, otherwise an unrelated in-service interrupt is acknowledged.
+    #[inline]
+    pub unsafe fn notify_end_of_interrupt(&mut self, vector: u8) {
+        if self.handles_interrupt(vector) {
+            unsafe {
+                if self.slave.handles_interrupt(vector) {
+                    self.slave.end_of_interrupt();
+                }
+                self.master.end_of_interrupt();
+            }
+        }
+    }
+
+    fn data_port(&mut self, irq: u8) -> &mut Port<u8> {
+        assert!(irq < 16, "the PICs only have 16 IRQ lines");
+        if irq < 8 {
+            &mut self.master.data
+        } else {
+            &mut self.slave.data
+        }
+    }
+}
@@ -160,6 +160,35 @@ impl<T: PortRead, A: PortReadAccess> PortGeneric<T, A> {
     }
 }
 
+impl<A: PortReadAccess> PortGeneric<u32, A> {
+    /// Reads `buf.len()` values from the port into the given buffer.
+    ///
+    /// Returns the number of `u32` values read, not the number of bytes. This is always
+    /// `buf.len()`, since `rep insd` can't stop early.
+    ///
+    /// The transfer is done by a single `rep insd` instruction, which avoids the overhead of
+    /// calling [`read`](Self::read) in a loop for bulk transfers, e.g. in PIO disk drivers.
+    ///
+    /// ## Safety
+    ///
+    /// This function is unsafe because the I/O port could have side effects that violate memory
+    /// safety.
+    #[doc(alias = "insd")]
+    #[inline]
+    pub unsafe fn read_buffer(&mut self, buf: &mut [u32]) -> usize {
+        unsafe {
+            asm!(
+                "rep insd",
+                in("dx") self.port,
+                inout("rdi") buf.as_mut_ptr() => _,
+                inout("rcx") buf.len() => _,
+                options(nostack, preserves_flags),
+            );
+        }
+        buf.len()
+    }
+}
+
 impl<T: PortWrite, A: PortWriteAccess> PortGeneric<T, A> {
     /// Writes to the port.
     ///
@@ -203,3 +232,85 @@ impl<T, A> PartialEq for PortGeneric<T, A> {
 }
 
 impl<T, A> Eq for PortGeneric<T, A> {}
+
+/// A read-write I/O port that counts the number of reads and writes performed through it.
+///
+/// This is a debugging aid for device drivers, e.g. to find out how often a status register is
+/// polled. It delegates to a [`Port`] and only adds the cost of incrementing a counter per
+/// access. Code that uses [`Port`] directly is not affected.
+#[derive(Debug)]
+pub struct InstrumentedPort<T> {
+    port: Port<T>,
+    reads: u64,
+    writes: u64,
+}
+
+impl<T> InstrumentedPort<T> {
+    /// Creates an instrumented I/O port with the given port number and zeroed counters.
+    #[inline]
+    pub const fn new(port: u16) -> InstrumentedPort<T> {
+        InstrumentedPort {
+            port: Port::new(port),
+            reads: 0,
+            writes: 0,
+        }
+    }
+
+    /// Returns the number of reads performed through this port.
+    #[inline]
+    pub const fn reads(&self) -> u64 {
+        self.reads
+    }
+
+    /// Returns the number of writes performed through this port.
+    #[inline]
+    pub const fn writes(&self) -> u64 {
+        self.writes
+    }
+
+    /// Resets the read and write counters to zero.
+    #[inline]
+    pub fn reset_counters(&mut self) {
+        self.reads = 0;
+        self.writes = 0;
+    }
+}
+
+impl<T: PortRead> InstrumentedPort<T> {
+    /// Reads from the port and increments the read counter.
+    ///
+    /// ## Safety
+    ///
+    /// This function is unsafe because the I/O port could have side effects that violate memory
+    /// safety.
+    #[inline]
+    pub unsafe fn read(&mut self) -> T {
+        self.reads = self.reads.wrapping_add(1);
+        unsafe { self.port.read() }
+    }
+}
+
+impl<T: PortWrite> InstrumentedPort<T> {
+    /// Writes to the port and increments the write counter.
+    ///
+    /// ## Safety
+    ///
+    /// This function is unsafe because the I/O port could have side effects that violate memory
+    /// safety.
+    #[inline]
+    pub unsafe fn write(&mut self, value: T) {
+        self.writes = self.writes.wrapping_add(1);
+        unsafe { self.port.write(value) }
+    }
+}
+
+impl<T> From<Port<T>> for InstrumentedPort<T> {
+    #[inline]
+    fn from(port: Port<T>) -> Self {
+        InstrumentedPort {
+            port,
+            reads: 0,
+            writes: 0,
+        }
+    }
+}
@@ -1,5 +1,54 @@
 //! Support for build-in RNGs
 
+use core::fmt;
+
+/// The number of times a failed `rdrand` is retried by [`RdRand::fill`].
+///
+/// Intel guarantees that `rdrand` succeeds at least once in 10 attempts unless the hardware is
+/// broken.
+pub const RDRAND_RETRIES: usize = 10;
+
+/// The number of times a failed `rdseed` is retried by [`RdSeed::fill`].
+///
+/// Unlike `rdrand`, `rdseed` can fail repeatedly if the entropy source is exhausted, so more
+/// retries with a short pause between them are used.
+pub const RDSEED_RETRIES: usize = 100;
+
+/// The error returned by the `fill` methods if the hardware RNG didn't provide enough random
+/// data within the retry limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RandError;
+
+impl fmt::Display for RandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the hardware random number generator failed")
+    }
+}
+
+/// Fills `buf` with the values returned by `next`, calling it up to `retries + 1` times for each
+/// 8-byte chunk.
+fn fill_with(
+    buf: &mut [u8],
+    retries: usize,
+    mut next: impl FnMut() -> Option<u64>,
+) -> Result<(), RandError> {
+    for chunk in buf.chunks_mut(8) {
+        let mut attempts = 0;
+        let value = loop {
+            if let Some(value) = next() {
+                break value;
+            }
+            if attempts == retries {
+                return Err(RandError);
+            }
+            attempts += 1;
+            core::hint::spin_loop();
+        };
+        chunk.copy_from_slice(&value.to_ne_bytes()[..chunk.len()]);
+    }
+    Ok(())
+}
+
 #[derive(Copy, Clone, Debug)]
 /// Used to obtain random numbers using x86_64's RDRAND opcode
 pub struct RdRand(());
@@ -63,6 +112,66 @@ impl RdRand {
             }
         }
     }
+
+    /// Fills the buffer with random bytes.
+    ///
+    /// The bytes are obtained through 64-bit reads, the last of which is truncated if the length
+    /// of the buffer is not a multiple of 8. Each failed read is retried up to
+    /// [`RDRAND_RETRIES`] times before an error is returned. On error, the contents of the
+    /// buffer are unspecified.
+    #[inline]
+    pub fn fill(self, buf: &mut [u8]) -> Result<(), RandError> {
+        fill_with(buf, RDRAND_RETRIES, || self.get_u64())
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+/// Used to obtain random seeds using x86_64's RDSEED opcode
+///
+/// In contrast to [`RdRand`], the values are taken directly from the entropy source instead of
+/// from a deterministic generator seeded by it, which makes them suitable for seeding other
+/// random number generators. `rdseed` fails more often than `rdrand` as a result.
+pub struct RdSeed(());
+
+impl RdSeed {
+    /// Creates Some(RdSeed) if RDSEED is supported, None otherwise
+    #[inline]
+    pub fn new() -> Option<Self> {
+        // RDSEED support indicated by CPUID page 07h, ebx bit 18
+        let max_leaf = unsafe { core::arch::x86_64::__cpuid(0x0) }.eax;
+        if max_leaf >= 0x7 && unsafe { core::arch::x86_64::__cpuid(0x7) }.ebx & (1 << 18) != 0 {
+            Some(RdSeed(()))
+        } else {
+            None
+        }
+    }
+
+    /// Random seed u64.
+    /// May fail if the entropy source is temporarily exhausted.
+    #[inline]
+    pub fn get_u64(self) -> Option<u64> {
+        let mut res: u64 = 0;
+        unsafe {
+            match core::arch::x86_64::_rdseed64_step(&mut res) {
+                1 => Some(res),
+                x => {
+                    debug_assert_eq!(x, 0, "rdseed64 returned non-binary value");
+                    None
+                }
+            }
+        }
+    }
+
+    /// Fills the buffer with random seed bytes.
+    ///
+    /// The bytes are obtained through 64-bit reads, the last of which is truncated if the length
+    /// of the buffer is not a multiple of 8. Each failed read is retried up to
+    /// [`RDSEED_RETRIES`] times before an error is returned. On error, the contents of the
+    /// buffer are unspecified.
+    #[inline]
+    pub fn fill(self, buf: &mut [u8]) -> Result<(), RandError> {
+        fill_with(buf, RDSEED_RETRIES, || self.get_u64())
+    }
 }
 
 #[cfg(test)]
@@ -77,8 +186,42 @@ mod tests {
             assert!(rand.get_u16().is_some());
             assert!(rand.get_u32().is_some());
             assert!(rand.get_u64().is_some());
+
+            let mut buf = [0; 13];
+            assert_eq!(rand.fill(&mut buf), Ok(()));
         } else {
             assert!(rand.is_none());
         }
     }
+
+    #[test]
+    pub fn test_rdseed() {
+        let seed = RdSeed::new();
+        if is_x86_feature_detected!("rdseed") {
+            let seed = seed.unwrap();
+            let mut buf = [0; 13];
+            assert_eq!(seed.fill(&mut buf), Ok(()));
+        } else {
+            assert!(seed.is_none());
+        }
+    }
+
+    #[test]
+    pub fn test_fill_with() {
+        let mut buf = [0; 11];
+        let mut values = [None, Some(u64::MAX), Some(0x0102_0304_0506_0708)]
+            .iter()
+            .copied();
+        assert_eq!(fill_with(&mut buf, 1, || values.next().flatten()), Ok(()));
+        assert_eq!(buf[..8], [0xff; 8]);
+        assert_eq!(buf[8..], 0x0102_0304_0506_0708u64.to_ne_bytes()[..3]);
+
+        let mut fails = 0;
+        let result = fill_with(&mut buf, 2, || {
+            fails += 1;
+            None
+        });
+        assert_eq!(result, Err(RandError));
+        assert_eq!(fails, 3);
+    }
 }
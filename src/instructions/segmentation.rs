@@ -1,7 +1,10 @@
 //! Provides functions to read and write segment registers.
 
-pub use crate::registers::segmentation::{Segment, Segment64, CS, DS, ES, FS, GS, SS};
+pub use crate::registers::segmentation::{
+    Segment, Segment64, SegmentRegisters, CS, DS, ES, FS, GS, SS,
+};
 use crate::{
+    registers::control::Cr4Flags,
     registers::model_specific::{FsBase, GsBase, Msr},
     structures::gdt::SegmentSelector,
     VirtAddr,
@@ -42,6 +45,7 @@ macro_rules! segment64_impl {
             const BASE: Msr = <$base>::MSR;
             #[inline]
             fn read_base() -> VirtAddr {
+                super::assert_cr4(Cr4Flags::FSGSBASE);
                 unsafe {
                     let val: u64;
                     asm!(concat!("rd", $name, "base {}"), out(reg) val, options(nomem, nostack, preserves_flags));
@@ -51,6 +55,12 @@ macro_rules! segment64_impl {
 
             #[inline]
             unsafe fn write_base(base: VirtAddr) {
+                super::assert_cr4(Cr4Flags::FSGSBASE);
+                assert!(
+                    VirtAddr::try_new(base.as_u64()).is_ok(),
+                    concat!("{:#x} is not a canonical ", $name, " base"),
+                    base.as_u64()
+                );
                 unsafe{
                     asm!(concat!("wr", $name, "base {}"), in(reg) base.as_u64(), options(nostack, preserves_flags));
                 }
@@ -87,6 +97,42 @@ impl Segment for CS {
     }
 }
 
+impl CS {
+    /// Loads `sel` into [`CS`] and jumps to `continue_at`.
+    ///
+    /// Like [`CS::set_reg`], this uses a far return (`retfq`), since [`CS`] can only be changed
+    /// together with the instruction pointer. Instead of continuing after the call, the far
+    /// return transfers control to `continue_at`, which therefore must never return. This is
+    /// useful when the code segment changes in a way that requires a fresh start, e.g. when
+    /// switching to a new GDT during early boot.
+    ///
+    /// Before the far return, the stack pointer is aligned to 16 bytes and a zero return address
+    /// is pushed, so `continue_at` is entered with the stack alignment expected by the C calling
+    /// convention. Everything on the stack of the caller is left in place, but is no longer
+    /// reachable through normal returns.
+    ///
+    /// ## Safety
+    ///
+    /// This function is unsafe because the caller must ensure that `sel` points to a valid
+    /// 64-bit code segment in the currently loaded GDT and that the current stack is usable by
+    /// `continue_at`.
+    #[inline]
+    pub unsafe fn reload(sel: SegmentSelector, continue_at: extern "C" fn() -> !) -> ! {
+        unsafe {
+            asm!(
+                "and rsp, -16",
+                "push 0",
+                "push {sel}",
+                "push {target}",
+                "retfq",
+                sel = in(reg) u64::from(sel.0),
+                target = in(reg) continue_at,
+                options(noreturn),
+            );
+        }
+    }
+}
+
 segment_impl!(SS, "ss");
 segment_impl!(DS, "ds");
 segment_impl!(ES, "es");
@@ -109,3 +155,157 @@ impl GS {
         }
     }
 }
+
+impl SegmentRegisters {
+    /// Reads the selectors of all six segment registers.
+    ///
+    /// For [`FS`] and [`GS`], this reads the selector through `mov`, not the
+    /// segment base.
+    #[inline]
+    pub fn snapshot() -> Self {
+        Self {
+            cs: CS::get_reg(),
+            ss: SS::get_reg(),
+            ds: DS::get_reg(),
+            es: ES::get_reg(),
+            fs: FS::get_reg(),
+            gs: GS::get_reg(),
+        }
+    }
+}
+
+/// A value that can be read from and written to an address relative to the [`FS`] or [`GS`]
+/// segment base.
+///
+/// This trait is implemented for the integer types that fit into a general purpose register.
+pub trait SegmentRelative: Copy {
+    /// Reads a value at the given offset from the [`FS`] segment base.
+    ///
+    /// ## Safety
+    ///
+    /// See [`read_fs`].
+    unsafe fn read_fs(offset: u64) -> Self;
+
+    /// Writes a value at the given offset from the [`FS`] segment base.
+    ///
+    /// ## Safety
+    ///
+    /// See [`write_fs`].
+    unsafe fn write_fs(offset: u64, value: Self);
+
+    /// Reads a value at the given offset from the [`GS`] segment base.
+    ///
+    /// ## Safety
+    ///
+    /// See [`read_gs`].
+    unsafe fn read_gs(offset: u64) -> Self;
+
+    /// Writes a value at the given offset from the [`GS`] segment base.
+    ///
+    /// ## Safety
+    ///
+    /// See [`write_gs`].
+    unsafe fn write_gs(offset: u64, value: Self);
+}
+
+macro_rules! segment_relative_impl {
+    ($type:ty, $class:ident, $modifier:literal, $size:literal) => {
+        impl SegmentRelative for $type {
+            #[inline]
+            unsafe fn read_fs(offset: u64) -> Self {
+                let value: $type;
+                unsafe {
+                    asm!(concat!("mov {0", $modifier, "}, ", $size, " ptr fs:[{1}]"), out($class) value, in(reg) offset, options(readonly, nostack, preserves_flags));
+                }
+                value
+            }
+
+            #[inline]
+            unsafe fn write_fs(offset: u64, value: Self) {
+                unsafe {
+                    asm!(concat!("mov ", $size, " ptr fs:[{1}], {0", $modifier, "}"), in($class) value, in(reg) offset, options(nostack, preserves_flags));
+                }
+            }
+
+            #[inline]
+            unsafe fn read_gs(offset: u64) -> Self {
+                let value: $type;
+                unsafe {
+                    asm!(concat!("mov {0", $modifier, "}, ", $size, " ptr gs:[{1}]"), out($class) value, in(reg) offset, options(readonly, nostack, preserves_flags));
+                }
+                value
+            }
+
+            #[inline]
+            unsafe fn write_gs(offset: u64, value: Self) {
+                unsafe {
+                    asm!(concat!("mov ", $size, " ptr gs:[{1}], {0", $modifier, "}"), in($class) value, in(reg) offset, options(nostack, preserves_flags));
+                }
+            }
+        }
+    };
+}
+
+segment_relative_impl!(u8, reg_byte, "", "byte");
+segment_relative_impl!(u16, reg, ":x", "word");
+segment_relative_impl!(u32, reg, ":e", "dword");
+segment_relative_impl!(u64, reg, "", "qword");
+
+/// Reads a value at the given offset from the [`FS`] segment base, using a `fs:[offset]`
+/// memory operand.
+///
+/// ## Safety
+///
+/// The caller must ensure that the [`FS`] base points to a valid memory region and that
+/// `offset` points to an initialized value of type `T` inside it, e.g. a field of a per-thread
+/// data structure.
+#[inline]
+pub unsafe fn read_fs<T: SegmentRelative>(offset: u64) -> T {
+    unsafe { T::read_fs(offset) }
+}
+
+/// Writes a value at the given offset from the [`FS`] segment base, using a `fs:[offset]`
+/// memory operand.
+///
+/// ## Safety
+///
+/// The caller must ensure that the [`FS`] base points to a valid memory region and that
+/// `offset` points to a value of type `T` inside it that can be overwritten without breaking
+/// memory safety.
+#[inline]
+pub unsafe fn write_fs<T: SegmentRelative>(offset: u64, value: T) {
+    unsafe { T::write_fs(offset, value) }
+}
+
+/// Reads a value at the given offset from the [`GS`] segment base, using a `gs:[offset]`
+/// memory operand.
+///
+/// Kernels typically point the [`GS`] base to a per-CPU data structure (swapping it with
+/// [`GS::swap`] on kernel entry), so this can be used to access per-CPU variables without
+/// knowing the address of the structure.
+///
+/// ## Safety
+///
+/// The caller must ensure that the [`GS`] base points to a valid memory region and that
+/// `offset` points to an initialized value of type `T` inside it. In particular, the kernel
+/// `GS` base must be active, e.g. `swapgs` must have been executed after an entry from user
+/// mode.
+#[inline]
+pub unsafe fn read_gs<T: SegmentRelative>(offset: u64) -> T {
+    unsafe { T::read_gs(offset) }
+}
+
+/// Writes a value at the given offset from the [`GS`] segment base, using a `gs:[offset]`
+/// memory operand.
+///
+/// See [`read_gs`] for more information.
+///
+/// ## Safety
+///
+/// The caller must ensure that the [`GS`] base points to a valid memory region and that
+/// `offset` points to a value of type `T` inside it that can be overwritten without breaking
+/// memory safety. In particular, the kernel `GS` base must be active.
+#[inline]
+pub unsafe fn write_gs<T: SegmentRelative>(offset: u64, value: T) {
+    unsafe { T::write_gs(offset, value) }
+}
@@ -0,0 +1,140 @@
+//! Minimal polling driver for a 16550 UART serial port.
+//!
+//! This is intended for early-boot debug output, e.g. to the first serial port of a virtual
+//! machine. It doesn't use interrupts or buffering; every byte is sent and received by polling
+//! the line status register. For a more complete driver, see the `uart_16550` crate.
+
+use core::fmt;
+
+use crate::instructions::port::Port;
+
+/// Set in the line control register to access the baud rate divisor through the data and
+/// interrupt enable registers.
+const LINE_CTRL_DLAB: u8 = 1 << 7;
+
+/// Line control value for 8 data bits, no parity, and one stop bit.
+const LINE_CTRL_8N1: u8 = 0x03;
+
+/// FIFO control value that enables and clears the FIFOs with a 14-byte interrupt threshold.
+const FIFO_CTRL_ENABLE: u8 = 0xC7;
+
+/// Modem control value that sets DTR, RTS, and OUT2.
+const MODEM_CTRL_READY: u8 = 0x0B;
+
+/// Set in the line status register when a received byte is available.
+const LINE_STS_INPUT_FULL: u8 = 1 << 0;
+
+/// Set in the line status register when the transmitter can accept a new byte.
+const LINE_STS_OUTPUT_EMPTY: u8 = 1 << 5;
+
+/// Baud rate divisor for 38400 baud, based on the 115200 Hz base clock.
+const BAUD_DIVISOR: u16 = 3;
+
+/// A 16550 UART serial port accessed through I/O ports.
+///
+/// The port implements [`fmt::Write`], so it can be used with the `write!` macro.
+///
+/// ## Example
+///
+/// ```no_run
+/// use core::fmt::Write;
+/// use x86_64::instructions::serial::SerialPort;
+///
+/// let mut serial = unsafe { SerialPort::new(0x3F8) };
+/// serial.init();
+/// writeln!(serial, "Hello from the kernel").unwrap();
+/// ```
+#[derive(Debug)]
+pub struct SerialPort {
+    data: Port<u8>,
+    int_en: Port<u8>,
+    fifo_ctrl: Port<u8>,
+    line_ctrl: Port<u8>,
+    modem_ctrl: Port<u8>,
+    line_sts: Port<u8>,
+}
+
+impl SerialPort {
+    /// Creates a new serial port for the UART with the given base I/O port, e.g. `0x3F8` for
+    /// the first serial port (`COM1`).
+    ///
+    /// The port must be initialized through [`init`](Self::init) before use.
+    ///
+    /// ## Safety
+    ///
+    /// This function is unsafe because the caller must ensure that a 16550-compatible UART is
+    /// present at the given base port and that it is not accessed through other means at the
+    /// same time.
+    #[inline]
+    pub const unsafe fn new(base: u16) -> Self {
+        SerialPort {
+            data: Port::new(base),
+            int_en: Port::new(base + 1),
+            fifo_ctrl: Port::new(base + 2),
+            line_ctrl: Port::new(base + 3),
+            modem_ctrl: Port::new(base + 4),
+            line_sts: Port::new(base + 5),
+        }
+    }
+
+    /// Initializes the UART for 38400 baud with 8 data bits, no parity, and one stop bit.
+    ///
+    /// Interrupts of the UART are disabled, since this type only supports polling.
+    pub fn init(&mut self) {
+        let [divisor_low, divisor_high] = BAUD_DIVISOR.to_le_bytes();
+        unsafe {
+            self.int_en.write(0x00);
+
+            self.line_ctrl.write(LINE_CTRL_DLAB);
+            self.data.write(divisor_low);
+            self.int_en.write(divisor_high);
+            self.line_ctrl.write(LINE_CTRL_8N1);
+
+            self.fifo_ctrl.write(FIFO_CTRL_ENABLE);
+            self.modem_ctrl.write(MODEM_CTRL_READY);
+        }
+    }
+
+    /// Sends a byte, waiting until the transmitter is ready.
+    #[inline]
+    pub fn send_byte(&mut self, byte: u8) {
+        while self.line_status() & LINE_STS_OUTPUT_EMPTY == 0 {
+            core::hint::spin_loop();
+        }
+        unsafe { self.data.write(byte) }
+    }
+
+    /// Receives a byte, waiting until one is available.
+    #[inline]
+    pub fn receive_byte(&mut self) -> u8 {
+        loop {
+            if let Some(byte) = self.try_receive_byte() {
+                return byte;
+            }
+            core::hint::spin_loop();
+        }
+    }
+
+    /// Receives a byte if one is available, without waiting.
+    #[inline]
+    pub fn try_receive_byte(&mut self) -> Option<u8> {
+        if self.line_status() & LINE_STS_INPUT_FULL != 0 {
+            Some(unsafe { self.data.read() })
+        } else {
+            None
+        }
+    }
+
+    fn line_status(&mut self) -> u8 {
+        unsafe { self.line_sts.read() }
+    }
+}
+
+impl fmt::Write for SerialPort {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes() {
+            self.send_byte(byte);
+        }
+        Ok(())
+    }
+}
@@ -17,8 +17,18 @@ pub use crate::structures::DescriptorTablePointer;
 /// This function is unsafe because the caller must ensure that the given
 /// `DescriptorTablePointer` points to a valid GDT and that loading this
 /// GDT is safe.
+///
+/// In debug builds, this function panics if the base address is not canonical or if the limit
+/// is zero, since a GDT always contains at least the null descriptor.
 #[inline]
 pub unsafe fn lgdt(gdt: &DescriptorTablePointer) {
+    let (base, limit) = (gdt.base, gdt.limit);
+    debug_assert!(
+        VirtAddr::try_new(base.as_u64()).is_ok(),
+        "GDT base {:#x} is not canonical",
+        base.as_u64()
+    );
+    debug_assert!(limit != 0, "GDT limit must not be zero");
     unsafe {
         asm!("lgdt [{}]", in(reg) gdt, options(readonly, nostack, preserves_flags));
     }
@@ -35,8 +45,17 @@ pub unsafe fn lgdt(gdt: &DescriptorTablePointer) {
 /// This function is unsafe because the caller must ensure that the given
 /// `DescriptorTablePointer` points to a valid IDT and that loading this
 /// IDT is safe.
+///
+/// In debug builds, this function panics if the base address is not canonical. A limit of zero
+/// is allowed, as an empty IDT is sometimes loaded on purpose to force a triple fault.
 #[inline]
 pub unsafe fn lidt(idt: &DescriptorTablePointer) {
+    let base = idt.base;
+    debug_assert!(
+        VirtAddr::try_new(base.as_u64()).is_ok(),
+        "IDT base {:#x} is not canonical",
+        base.as_u64()
+    );
     unsafe {
         asm!("lidt [{}]", in(reg) idt, options(readonly, nostack, preserves_flags));
     }
@@ -78,6 +97,7 @@ pub fn sidt() -> DescriptorTablePointer {
 /// for more information.
 ///
 /// Calling `load_tss` with a busy TSS selector results in a `#GP` exception.
+/// Use [`reload_tss`] to clear the busy bit before loading the TSS again.
 ///
 /// ## Safety
 ///
@@ -90,3 +110,58 @@ pub unsafe fn load_tss(sel: SegmentSelector) {
         asm!("ltr {0:x}", in(reg) sel.0, options(nostack, preserves_flags));
     }
 }
+
+/// Load the given GDT and then the task state register using the given TSS selector.
+///
+/// This combines [`GlobalDescriptorTable::load`](crate::structures::gdt::GlobalDescriptorTable::load)
+/// and [`load_tss`]. The selector is typically obtained through
+/// [`GlobalDescriptorTable::append_tss`](crate::structures::gdt::GlobalDescriptorTable::append_tss).
+/// Like `GlobalDescriptorTable::load`, this does **not** reload any segment
+/// registers.
+///
+/// ## Safety
+///
+/// This function is unsafe because the caller must ensure that the given
+/// `SegmentSelector` points to a valid, non-busy TSS entry in the given GDT
+/// and that the corresponding data in the TSS is valid.
+#[inline]
+pub unsafe fn load_tss_with_gdt<const MAX: usize>(
+    gdt: &'static crate::structures::gdt::GlobalDescriptorTable<MAX>,
+    sel: SegmentSelector,
+) {
+    gdt.load();
+    unsafe {
+        load_tss(sel);
+    }
+}
+
+/// Clear the busy bit of the given TSS descriptor and then load the task state
+/// register using the `ltr` instruction.
+///
+/// Loading a TSS marks its descriptor as busy, so calling [`load_tss`] a second
+/// time with the same selector results in a `#GP` exception. This function
+/// resets the busy bit through
+/// [`GlobalDescriptorTable::clear_tss_busy`](crate::structures::gdt::GlobalDescriptorTable::clear_tss_busy)
+/// first, which allows reloading a TSS, for example when switching back to a
+/// previously used TSS or when re-entering a kernel after a warm reset.
+///
+/// The given GDT must be the currently loaded GDT.
+///
+/// Panics if the selector does not point to a TSS descriptor in the given GDT.
+///
+/// ## Safety
+///
+/// This function is unsafe because the caller must ensure that the given
+/// `SegmentSelector` points to a valid TSS entry in the currently loaded GDT,
+/// that the corresponding data in the TSS is valid, and that the TSS is not
+/// currently in use on another CPU.
+#[inline]
+pub unsafe fn reload_tss<const MAX: usize>(
+    gdt: &'static crate::structures::gdt::GlobalDescriptorTable<MAX>,
+    sel: SegmentSelector,
+) {
+    gdt.clear_tss_busy(sel);
+    unsafe {
+        load_tss(sel);
+    }
+}
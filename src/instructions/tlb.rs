@@ -4,6 +4,7 @@ use bit_field::BitField;
 
 use crate::{
     instructions::segmentation::{Segment, CS},
+    registers::control::Cr4Flags,
     structures::paging::{
         page::{NotGiantPageSize, PageRange},
         Page, PageSize, Size2MiB, Size4KiB,
@@ -21,11 +22,38 @@ pub fn flush(addr: VirtAddr) {
 }
 
 /// Invalidate the TLB completely by reloading the CR3 register.
+///
+/// Note that this does not invalidate entries of pages mapped with the `GLOBAL` flag while
+/// `Cr4Flags::PAGE_GLOBAL` is set. Use [`flush_including_global`] for that.
+///
+/// This is equivalent to [`Cr3::reload`](crate::registers::control::Cr3::reload).
 #[inline]
 pub fn flush_all() {
-    use crate::registers::control::Cr3;
-    let (frame, flags) = Cr3::read();
-    unsafe { Cr3::write(frame, flags) }
+    crate::registers::control::Cr3::reload();
+}
+
+/// Invalidate the TLB completely, including the entries of global pages.
+///
+/// If global pages are enabled, this toggles the `Cr4Flags::PAGE_GLOBAL` flag off and on
+/// again, which is the standard way to invalidate global entries. Otherwise there are no
+/// global entries and the CR3 register is reloaded like in [`flush_all`]. This is needed after
+/// changing a mapping that has the `GLOBAL` flag set.
+#[inline]
+pub fn flush_including_global() {
+    use crate::instructions::interrupts;
+    use crate::registers::control::{Cr4, Cr4Flags};
+
+    interrupts::without_interrupts(|| {
+        let flags = Cr4::read();
+        if flags.contains(Cr4Flags::PAGE_GLOBAL) {
+            unsafe {
+                Cr4::write(flags - Cr4Flags::PAGE_GLOBAL);
+                Cr4::write(flags);
+            }
+        } else {
+            flush_all();
+        }
+    })
 }
 
 /// The Invalidate PCID Command to execute.
@@ -94,9 +122,14 @@ impl fmt::Display for PcidTooBig {
 
 /// Invalidate the given address in the TLB using the `invpcid` instruction.
 ///
+/// In debug builds, this panics if `command` has a non-zero PCID and `Cr4Flags::PCID` is not
+/// set, see [`assert_cr4`](super::assert_cr4).
+///
 /// ## Safety
 ///
 /// This function is unsafe as it requires CPUID.(EAX=07H, ECX=0H):EBX.INVPCID to be 1.
+/// Additionally, `Cr4Flags::PCID` must be set for the `Address` and `Single` commands if
+/// their PCID is not 0, otherwise a `#GP` exception is raised.
 #[inline]
 pub unsafe fn flush_pcid(command: InvPcidCommand) {
     let mut desc = InvpcidDescriptor {
@@ -118,12 +151,63 @@ pub unsafe fn flush_pcid(command: InvPcidCommand) {
         InvPcidCommand::All => kind = 2,
         InvPcidCommand::AllExceptGlobal => kind = 3,
     }
+    if kind <= 1 && desc.pcid != 0 {
+        super::assert_cr4(Cr4Flags::PCID);
+    }
 
     unsafe {
         asm!("invpcid {0}, [{1}]", in(reg) kind, in(reg) &desc, options(nostack, preserves_flags));
     }
 }
 
+/// Invalidate the mapping of the given address for the given PCID, except global
+/// translations, using `invpcid` type 0.
+///
+/// ## Safety
+///
+/// This function is unsafe as it requires CPUID.(EAX=07H, ECX=0H):EBX.INVPCID to be 1.
+/// Additionally, `Cr4Flags::PCID` must be set if `pcid` is not 0, otherwise a `#GP`
+/// exception is raised.
+#[inline]
+pub unsafe fn flush_addr_pcid(pcid: Pcid, addr: VirtAddr) {
+    unsafe { flush_pcid(InvPcidCommand::Address(addr, pcid)) }
+}
+
+/// Invalidate all mappings associated with the given PCID, except global translations,
+/// using `invpcid` type 1.
+///
+/// ## Safety
+///
+/// This function is unsafe as it requires CPUID.(EAX=07H, ECX=0H):EBX.INVPCID to be 1.
+/// Additionally, `Cr4Flags::PCID` must be set if `pcid` is not 0, otherwise a `#GP`
+/// exception is raised.
+#[inline]
+pub unsafe fn flush_single_context(pcid: Pcid) {
+    unsafe { flush_pcid(InvPcidCommand::Single(pcid)) }
+}
+
+/// Invalidate all mappings associated with any PCID, including global translations, using
+/// `invpcid` type 2.
+///
+/// ## Safety
+///
+/// This function is unsafe as it requires CPUID.(EAX=07H, ECX=0H):EBX.INVPCID to be 1.
+#[inline]
+pub unsafe fn flush_all_including_global() {
+    unsafe { flush_pcid(InvPcidCommand::All) }
+}
+
+/// Invalidate all mappings associated with any PCID, except global translations, using
+/// `invpcid` type 3.
+///
+/// ## Safety
+///
+/// This function is unsafe as it requires CPUID.(EAX=07H, ECX=0H):EBX.INVPCID to be 1.
+#[inline]
+pub unsafe fn flush_all_excluding_global() {
+    unsafe { flush_pcid(InvPcidCommand::AllExceptGlobal) }
+}
+
 /// Used to broadcast flushes to all logical processors.
 ///
 /// ```no_run
@@ -9,7 +9,7 @@
 #![deny(missing_debug_implementations)]
 #![deny(unsafe_op_in_unsafe_fn)]
 
-pub use crate::addr::{align_down, align_up, PhysAddr, VirtAddr};
+pub use crate::addr::{align_down, align_up, PhysAddr, PhysOffset, VirtAddr};
 
 pub mod addr;
 pub mod instructions;
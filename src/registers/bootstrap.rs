@@ -0,0 +1,95 @@
+//! Functions to enable CPU features that require a specific sequence of register writes.
+
+#[cfg(all(feature = "instructions", target_arch = "x86_64"))]
+pub use self::x86_64::*;
+
+use core::fmt;
+
+/// The error returned by `enable_sse` if the CPU doesn't support `FXSAVE` or SSE.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SseNotSupported;
+
+impl fmt::Display for SseNotSupported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the CPU doesn't support FXSAVE or SSE")
+    }
+}
+
+/// The error returned by `enable_avx` if the CPU doesn't support `XSAVE` or AVX.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AvxNotSupported;
+
+impl fmt::Display for AvxNotSupported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the CPU doesn't support XSAVE or AVX")
+    }
+}
+
+#[cfg(all(feature = "instructions", target_arch = "x86_64"))]
+mod x86_64 {
+    use super::*;
+    use crate::registers::control::{Cr0, Cr4, Cr4Flags};
+    use crate::registers::xcontrol::{XCr0, XCr0Flags};
+
+    /// Enables SSE instructions.
+    ///
+    /// This checks `cpuid` for `FXSAVE` and SSE support, then clears
+    /// [`Cr0Flags::EMULATE_COPROCESSOR`](crate::registers::control::Cr0Flags::EMULATE_COPROCESSOR),
+    /// sets
+    /// [`Cr0Flags::MONITOR_COPROCESSOR`](crate::registers::control::Cr0Flags::MONITOR_COPROCESSOR) (see
+    /// [`Cr0::clear_emulation_for_fpu`]), and sets [`Cr4Flags::OSFXSR`] and
+    /// [`Cr4Flags::OSXMMEXCPT_ENABLE`]. Until this is done, every SSE instruction causes an
+    /// invalid opcode exception (`#UD`). Note that the compiler may emit SSE instructions for
+    /// ordinary code, e.g. for copying larger structs, so this should be called as early as
+    /// possible if the code is compiled with SSE enabled.
+    ///
+    /// Returns an error without changing any register if either feature is not supported.
+    ///
+    /// ## Safety
+    ///
+    /// This function is unsafe because the caller must ensure that the SSE state is saved and
+    /// restored on context switches, e.g. with `fxsave`, and that SIMD floating-point
+    /// exceptions (`#XM`) are handled. It must also not be relied upon that x87/MMX
+    /// instructions cause an exception.
+    pub unsafe fn enable_sse() -> Result<(), SseNotSupported> {
+        // FXSAVE support is indicated by CPUID page 01h, edx bit 24, SSE support by edx bit 25
+        let cpuid = unsafe { core::arch::x86_64::__cpuid(0x1) };
+        if cpuid.edx & (1 << 24) == 0 || cpuid.edx & (1 << 25) == 0 {
+            return Err(SseNotSupported);
+        }
+
+        unsafe {
+            Cr0::clear_emulation_for_fpu();
+            Cr4::update(|flags| flags.insert(Cr4Flags::OSFXSR | Cr4Flags::OSXMMEXCPT_ENABLE));
+        }
+        Ok(())
+    }
+
+    /// Enables AVX instructions.
+    ///
+    /// This checks `cpuid` for `XSAVE` and AVX support, then sets [`Cr4Flags::OSXSAVE`] and
+    /// afterwards the [`X87`](XCr0Flags::X87), [`SSE`](XCr0Flags::SSE) and
+    /// [`AVX`](XCr0Flags::AVX) flags in XCR0. The order matters: accessing XCR0 before
+    /// `OSXSAVE` is set causes an invalid opcode exception (`#UD`).
+    ///
+    /// Returns an error without changing any register if either feature is not supported.
+    ///
+    /// ## Safety
+    ///
+    /// This function is unsafe because the caller must ensure that SSE is enabled as well,
+    /// e.g. through [`enable_sse`], and that the extended state is saved and restored on
+    /// context switches, e.g. with `xsave`.
+    pub unsafe fn enable_avx() -> Result<(), AvxNotSupported> {
+        // XSAVE support is indicated by CPUID page 01h, ecx bit 26, AVX support by ecx bit 28
+        let cpuid = unsafe { core::arch::x86_64::__cpuid(0x1) };
+        if cpuid.ecx & (1 << 26) == 0 || cpuid.ecx & (1 << 28) == 0 {
+            return Err(AvxNotSupported);
+        }
+
+        unsafe {
+            Cr4::update(|flags| flags.insert(Cr4Flags::OSXSAVE));
+            XCr0::update(|flags| flags.insert(XCr0Flags::X87 | XCr0Flags::SSE | XCr0Flags::AVX));
+        }
+        Ok(())
+    }
+}
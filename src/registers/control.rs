@@ -1,6 +1,10 @@
 //! Functions to read and write control registers.
 
+#[cfg(all(feature = "instructions", target_arch = "x86_64"))]
+pub use super::bootstrap::enable_sse;
+pub use super::bootstrap::SseNotSupported;
 pub use super::model_specific::{Efer, EferFlags};
+
 use bitflags::bitflags;
 
 /// Various control flags modifying the basic operation of the CPU.
@@ -223,7 +227,8 @@ mod x86_64 {
 
         /// Updates CR0 flags.
         ///
-        /// Preserves the value of reserved fields.
+        /// Preserves the value of reserved fields. This is the preferred way to change a single
+        /// flag, since the other flags are left untouched.
         ///
         /// ## Safety
         ///
@@ -240,6 +245,68 @@ mod x86_64 {
                 Self::write(flags);
             }
         }
+
+        /// Sets the [`WRITE_PROTECT`](Cr0Flags::WRITE_PROTECT) flag.
+        ///
+        /// Afterwards, writes from ring 0 to read-only pages cause a page fault.
+        ///
+        /// Preserves the value of reserved fields.
+        ///
+        /// ## Safety
+        ///
+        /// The caller must ensure that no code running in ring 0 relies on being
+        /// able to write to pages that are mapped read-only.
+        #[inline]
+        pub unsafe fn enable_write_protect() {
+            unsafe {
+                Self::update(|flags| flags.insert(Cr0Flags::WRITE_PROTECT));
+            }
+        }
+
+        /// Sets or clears the [`TASK_SWITCHED`](Cr0Flags::TASK_SWITCHED) flag.
+        ///
+        /// While the flag is set, the first x87/MMX/SSE instruction causes a
+        /// device-not-available (`#NM`) exception, which can be used to lazily
+        /// save and restore the FPU state on context switches. Clearing the flag
+        /// is equivalent to the `clts` instruction.
+        ///
+        /// Preserves the value of reserved fields.
+        ///
+        /// ## Safety
+        ///
+        /// When setting the flag, the caller must ensure that a handler for the
+        /// `#NM` exception is installed that restores the FPU state and clears
+        /// the flag again.
+        #[inline]
+        pub unsafe fn set_task_switched(value: bool) {
+            unsafe {
+                Self::update(|flags| flags.set(Cr0Flags::TASK_SWITCHED, value));
+            }
+        }
+
+        /// Prepares CR0 for using the x87 FPU and SSE instructions natively.
+        ///
+        /// Clears the [`EMULATE_COPROCESSOR`](Cr0Flags::EMULATE_COPROCESSOR) flag
+        /// and sets the [`MONITOR_COPROCESSOR`](Cr0Flags::MONITOR_COPROCESSOR) flag,
+        /// which is the configuration recommended by the Intel SDM for processors
+        /// with an integrated FPU. To use SSE instructions, [`Cr4Flags::OSFXSR`]
+        /// must be set in addition.
+        ///
+        /// Preserves the value of reserved fields.
+        ///
+        /// ## Safety
+        ///
+        /// The caller must ensure that no code relies on x87/MMX instructions
+        /// causing an exception, e.g. for software emulation of the FPU.
+        #[inline]
+        pub unsafe fn clear_emulation_for_fpu() {
+            unsafe {
+                Self::update(|flags| {
+                    flags.remove(Cr0Flags::EMULATE_COPROCESSOR);
+                    flags.insert(Cr0Flags::MONITOR_COPROCESSOR);
+                });
+            }
+        }
     }
 
     impl Cr2 {
@@ -418,6 +485,54 @@ mod x86_64 {
                 Self::write_pcid_no_flush(frame, pcid);
             }
         }
+
+        /// Writes the current value back into the CR3 register, flushing the TLB.
+        ///
+        /// The P4 table address and the lower 12 bits (i.e. the [`Cr3Flags`] or the [`Pcid`])
+        /// are left unchanged. The write flushes the TLB entries of the current PCID (or all
+        /// non-global TLB entries if PCIDs are disabled), which is useful after modifying many
+        /// entries of the active page table. Entries of pages mapped with the `GLOBAL` flag
+        /// survive the reload.
+        ///
+        /// This is what [`tlb::flush_all`](crate::instructions::tlb::flush_all) does.
+        #[inline]
+        pub fn reload() {
+            let (frame, val) = Self::read_raw();
+            unsafe {
+                Self::write_raw_impl(false, frame, val);
+            }
+        }
+
+        /// Temporarily switches to the given P4 table while running the closure.
+        ///
+        /// Saves the current P4 table address, writes `frame` into the CR3
+        /// register, runs `f`, and then restores the previous P4 table address.
+        /// The lower 12 bits of CR3 (i.e. the [`Cr3Flags`] or the [`Pcid`]) are
+        /// preserved for both switches.
+        ///
+        /// Both writes to CR3 flush the TLB entries of the current PCID (or all
+        /// non-global TLB entries if PCIDs are disabled) as a side effect.
+        ///
+        /// ## Safety
+        ///
+        /// Changing the level 4 page table is unsafe, because it's possible to violate memory safety by
+        /// changing the page mapping. The caller must ensure that the code and data used by `f`
+        /// (including the current stack) are mapped identically in the given P4 table.
+        #[inline]
+        pub unsafe fn with<F, R>(frame: PhysFrame, f: F) -> R
+        where
+            F: FnOnce() -> R,
+        {
+            let (old_frame, val) = Self::read_raw();
+            unsafe {
+                Self::write_raw(frame, val);
+            }
+            let result = f();
+            unsafe {
+                Self::write_raw(old_frame, val);
+            }
+            result
+        }
     }
 
     impl Cr4 {
@@ -477,7 +592,16 @@ mod x86_64 {
 
         /// Updates CR4 flags.
         ///
-        /// Preserves the value of reserved fields.
+        /// Preserves the value of reserved fields. This is the preferred way to change a single
+        /// flag, since the other flags are left untouched.
+        ///
+        /// ## Example
+        ///
+        /// ```no_run
+        /// use x86_64::registers::control::{Cr4, Cr4Flags};
+        ///
+        /// unsafe { Cr4::update(|flags| flags.insert(Cr4Flags::OSFXSR)) };
+        /// ```
         ///
         /// ## Safety
         ///
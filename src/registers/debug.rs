@@ -209,6 +209,7 @@ bitflags! {
         /// Enables debug register protection.
         ///
         /// This will cause a debug exception before any access to a debug register.
+        /// The processor clears this flag when entering the debug exception handler.
         const GENERAL_DETECT_ENABLE = 1 << 13;
     }
 }
@@ -512,5 +513,32 @@ mod x86_64 {
             f(&mut value);
             Self::write(value);
         }
+
+        /// Enable general detection by setting [`Dr7Flags::GENERAL_DETECT_ENABLE`].
+        ///
+        /// After this, any `mov` to or from a debug register causes a debug exception (`#DB`)
+        /// with [`Dr6Flags::ACCESS_DETECTED`] set, before the access is executed. This allows a
+        /// debugger to trap on accesses to the debug registers.
+        ///
+        /// The processor clears the flag when entering the `#DB` handler, so that the handler
+        /// itself can access the debug registers. The handler has to call this function again
+        /// to keep general detection enabled.
+        ///
+        /// To trap after each instruction instead, set
+        /// [`RFlags::TRAP_FLAG`](crate::registers::rflags::RFlags::TRAP_FLAG) to enable
+        /// single-step mode.
+        #[inline]
+        pub fn enable_general_detect() {
+            Self::update(|value| value.insert_flags(Dr7Flags::GENERAL_DETECT_ENABLE));
+        }
+
+        /// Disable general detection by clearing [`Dr7Flags::GENERAL_DETECT_ENABLE`].
+        ///
+        /// Note that this accesses DR7, so it causes a debug exception if general detection is
+        /// currently enabled and not called from the `#DB` handler.
+        #[inline]
+        pub fn disable_general_detect() {
+            Self::update(|value| value.remove_flags(Dr7Flags::GENERAL_DETECT_ENABLE));
+        }
     }
 }
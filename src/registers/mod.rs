@@ -1,9 +1,11 @@
 //! Access to various system and model specific registers.
 
+pub mod bootstrap;
 pub mod control;
 pub mod debug;
 pub mod model_specific;
 pub mod mxcsr;
+pub mod pkru;
 pub mod rflags;
 pub mod segmentation;
 pub mod xcontrol;
@@ -21,6 +21,49 @@ impl Msr {
     }
 }
 
+/// Well-known architectural model specific registers.
+///
+/// These constants give the common MSRs a discoverable name. The register types in the parent
+/// module, e.g. [`Efer`], provide typed access to some of them.
+pub mod consts {
+    use super::Msr;
+
+    /// IA32_APIC_BASE (`0x1B`): base address and enable bits of the local APIC.
+    pub const IA32_APIC_BASE: Msr = Msr(0x1B);
+    /// IA32_FEATURE_CONTROL (`0x3A`): controls VMX and SGX enablement.
+    pub const IA32_FEATURE_CONTROL: Msr = Msr(0x3A);
+    /// IA32_SPEC_CTRL (`0x48`): speculation control.
+    pub const IA32_SPEC_CTRL: Msr = Msr(0x48);
+    /// IA32_PRED_CMD (`0x49`): prediction barrier commands.
+    pub const IA32_PRED_CMD: Msr = Msr(0x49);
+    /// IA32_TIME_STAMP_COUNTER (`0x10`): the time-stamp counter.
+    pub const IA32_TSC: Msr = Msr(0x10);
+    /// IA32_MISC_ENABLE (`0x1A0`): enables miscellaneous processor features.
+    pub const IA32_MISC_ENABLE: Msr = Msr(0x1A0);
+    /// IA32_PAT (`0x277`): the page attribute table.
+    pub const IA32_PAT: Msr = Msr(0x277);
+    /// IA32_U_CET (`0x6A0`): user mode CET configuration.
+    pub const IA32_U_CET: Msr = Msr(0x6A0);
+    /// IA32_S_CET (`0x6A2`): supervisor mode CET configuration.
+    pub const IA32_S_CET: Msr = Msr(0x6A2);
+    /// IA32_EFER (`0xC000_0080`): the extended feature enable register.
+    pub const IA32_EFER: Msr = Msr(0xC000_0080);
+    /// IA32_STAR (`0xC000_0081`): segment selectors for `syscall` and `sysret`.
+    pub const IA32_STAR: Msr = Msr(0xC000_0081);
+    /// IA32_LSTAR (`0xC000_0082`): the `syscall` target address in 64-bit mode.
+    pub const IA32_LSTAR: Msr = Msr(0xC000_0082);
+    /// IA32_FMASK (`0xC000_0084`): the RFLAGS mask applied by `syscall`.
+    pub const IA32_FMASK: Msr = Msr(0xC000_0084);
+    /// IA32_FS_BASE (`0xC000_0100`): the base address of the FS segment.
+    pub const IA32_FS_BASE: Msr = Msr(0xC000_0100);
+    /// IA32_GS_BASE (`0xC000_0101`): the base address of the GS segment.
+    pub const IA32_GS_BASE: Msr = Msr(0xC000_0101);
+    /// IA32_KERNEL_GS_BASE (`0xC000_0102`): the value swapped into GS.Base by `swapgs`.
+    pub const IA32_KERNEL_GS_BASE: Msr = Msr(0xC000_0102);
+    /// IA32_TSC_AUX (`0xC000_0103`): the auxiliary value returned by `rdtscp` and `rdpid`.
+    pub const IA32_TSC_AUX: Msr = Msr(0xC000_0103);
+}
+
 /// The Extended Feature Enable Register.
 #[derive(Debug)]
 pub struct Efer;
@@ -67,49 +110,91 @@ pub struct UCet;
 #[derive(Debug)]
 pub struct SCet;
 
+/// IA32_FEATURE_CONTROL: controls VMX and SGX enablement
+#[derive(Debug)]
+pub struct FeatureControl;
+
+/// IA32_MISC_ENABLE: enables miscellaneous processor features (Intel only)
+#[derive(Debug)]
+pub struct MiscEnable;
+
+/// IA32_SPEC_CTRL: speculation control
+///
+/// This register exists if `cpuid` reports IBRS and IBPB support
+/// (`CPUID.(EAX=07H,ECX=0):EDX[26]` on Intel, `CPUID.80000008H:EBX[14]` on AMD).
+#[derive(Debug)]
+pub struct SpecCtrl;
+
+/// IA32_PRED_CMD: prediction barrier commands (write-only)
+///
+/// This register exists if `cpuid` reports IBPB support
+/// (`CPUID.(EAX=07H,ECX=0):EDX[26]` on Intel, `CPUID.80000008H:EBX[12]` on AMD).
+#[derive(Debug)]
+pub struct PredCmd;
+
 impl Efer {
     /// The underlying model specific register.
-    pub const MSR: Msr = Msr(0xC000_0080);
+    pub const MSR: Msr = consts::IA32_EFER;
 }
 
 impl FsBase {
     /// The underlying model specific register.
-    pub const MSR: Msr = Msr(0xC000_0100);
+    pub const MSR: Msr = consts::IA32_FS_BASE;
 }
 
 impl GsBase {
     /// The underlying model specific register.
-    pub const MSR: Msr = Msr(0xC000_0101);
+    pub const MSR: Msr = consts::IA32_GS_BASE;
 }
 
 impl KernelGsBase {
     /// The underlying model specific register.
-    pub const MSR: Msr = Msr(0xC000_0102);
+    pub const MSR: Msr = consts::IA32_KERNEL_GS_BASE;
 }
 
 impl Star {
     /// The underlying model specific register.
-    pub const MSR: Msr = Msr(0xC000_0081);
+    pub const MSR: Msr = consts::IA32_STAR;
 }
 
 impl LStar {
     /// The underlying model specific register.
-    pub const MSR: Msr = Msr(0xC000_0082);
+    pub const MSR: Msr = consts::IA32_LSTAR;
 }
 
 impl SFMask {
     /// The underlying model specific register.
-    pub const MSR: Msr = Msr(0xC000_0084);
+    pub const MSR: Msr = consts::IA32_FMASK;
 }
 
 impl UCet {
     /// The underlying model specific register.
-    pub const MSR: Msr = Msr(0x6A0);
+    pub const MSR: Msr = consts::IA32_U_CET;
 }
 
 impl SCet {
     /// The underlying model specific register.
-    pub const MSR: Msr = Msr(0x6A2);
+    pub const MSR: Msr = consts::IA32_S_CET;
+}
+
+impl FeatureControl {
+    /// The underlying model specific register.
+    pub const MSR: Msr = consts::IA32_FEATURE_CONTROL;
+}
+
+impl MiscEnable {
+    /// The underlying model specific register.
+    pub const MSR: Msr = consts::IA32_MISC_ENABLE;
+}
+
+impl SpecCtrl {
+    /// The underlying model specific register.
+    pub const MSR: Msr = consts::IA32_SPEC_CTRL;
+}
+
+impl PredCmd {
+    /// The underlying model specific register.
+    pub const MSR: Msr = consts::IA32_PRED_CMD;
 }
 
 bitflags! {
@@ -161,6 +246,118 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /// Flags stored in IA32_FEATURE_CONTROL (Table 2-2 in Intel SDM Volume 4).
+    ///
+    /// Once [`LOCKED`](FeatureControlFlags::LOCKED) is set, the register can no
+    /// longer be written until the next reset.
+    #[repr(transparent)]
+    #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+    pub struct FeatureControlFlags: u64 {
+        /// Locks the register. Any write after the lock bit is set causes a
+        /// general protection fault.
+        const LOCKED = 1 << 0;
+        /// Enables VMXON inside of SMX operation.
+        const VMX_INSIDE_SMX = 1 << 1;
+        /// Enables VMXON outside of SMX operation.
+        const VMX_OUTSIDE_SMX = 1 << 2;
+        /// Enables the SGX launch control configuration.
+        const SGX_LAUNCH_CONTROL_ENABLE = 1 << 17;
+        /// Enables SGX (SGX global enable).
+        const SGX_ENABLE = 1 << 18;
+        /// Enables local machine check exceptions.
+        const LMCE_ON = 1 << 20;
+    }
+}
+
+bitflags! {
+    /// Flags stored in IA32_MISC_ENABLE (Table 2-2 in Intel SDM Volume 4).
+    ///
+    /// This register only exists on Intel processors. Some of the flags are
+    /// read-only and writing a different value to them causes a general
+    /// protection fault, so they should only be changed through
+    /// [`MiscEnable::update`].
+    #[repr(transparent)]
+    #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+    pub struct MiscEnableFlags: u64 {
+        /// Enables fast-string operations for `rep movs` and `rep stos`.
+        const FAST_STRINGS = 1 << 0;
+        /// Enables the automatic thermal control circuit (TM1/TM2).
+        ///
+        /// Disabling it can cause the processor to overheat.
+        const AUTOMATIC_THERMAL_CONTROL = 1 << 3;
+        /// Indicates that performance monitoring is available (read-only).
+        const PERFORMANCE_MONITORING_AVAILABLE = 1 << 7;
+        /// Indicates that branch trace storage is unavailable (read-only).
+        const BRANCH_TRACE_STORAGE_UNAVAILABLE = 1 << 11;
+        /// Indicates that processor event based sampling is unavailable (read-only).
+        const PEBS_UNAVAILABLE = 1 << 12;
+        /// Enables Enhanced Intel SpeedStep Technology (P-states).
+        const ENHANCED_SPEEDSTEP = 1 << 16;
+        /// Enables the `monitor` and `mwait` instructions.
+        const ENABLE_MONITOR_FSM = 1 << 18;
+        /// Limits the maximum basic `cpuid` leaf to 2.
+        ///
+        /// Firmware sometimes sets this for legacy operating systems. It hides
+        /// most `cpuid` leaves, so it should be cleared.
+        const LIMIT_CPUID_MAXVAL = 1 << 22;
+        /// Disables xTPR messages.
+        const XTPR_MESSAGE_DISABLE = 1 << 23;
+        /// Disables the no-execute page-protection feature.
+        ///
+        /// If firmware set this flag, it must be cleared before
+        /// [`EferFlags::NO_EXECUTE_ENABLE`] can be set.
+        const XD_BIT_DISABLE = 1 << 34;
+    }
+}
+
+bitflags! {
+    /// Flags stored in IA32_SPEC_CTRL (Table 2-2 in Intel SDM Volume 4).
+    ///
+    /// Each flag is only supported if the corresponding `cpuid` bit is set; setting an
+    /// unsupported flag causes a general protection fault.
+    #[repr(transparent)]
+    #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+    pub struct SpecCtrlFlags: u64 {
+        /// Restricts indirect branch predictions (IBRS).
+        ///
+        /// Supported if `CPUID.(EAX=07H,ECX=0):EDX[26]` is set.
+        const IBRS = 1 << 0;
+        /// Prevents indirect branch predictions from being shared between logical processors
+        /// of the same core (STIBP).
+        ///
+        /// Supported if `CPUID.(EAX=07H,ECX=0):EDX[27]` is set.
+        const STIBP = 1 << 1;
+        /// Disables speculative store bypass (SSBD).
+        ///
+        /// Supported if `CPUID.(EAX=07H,ECX=0):EDX[31]` is set.
+        const SSBD = 1 << 2;
+    }
+}
+
+bitflags! {
+    /// Commands that can be written to IA32_PRED_CMD (Table 2-2 in Intel SDM Volume 4).
+    #[repr(transparent)]
+    #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+    pub struct PredCmdFlags: u64 {
+        /// Indirect branch prediction barrier (IBPB).
+        ///
+        /// Prevents indirect branch targets predicted before the barrier from influencing
+        /// predictions after it.
+        const IBPB = 1 << 0;
+    }
+}
+
+/// The error returned when writing to a locked IA32_FEATURE_CONTROL register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeatureControlLocked;
+
+impl core::fmt::Display for FeatureControlLocked {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "IA32_FEATURE_CONTROL is locked and can't be written.")
+    }
+}
+
 #[cfg(all(feature = "instructions", target_arch = "x86_64"))]
 mod x86_64 {
     use super::*;
@@ -173,6 +370,7 @@ mod x86_64 {
     use bit_field::BitField;
     use core::convert::TryInto;
     use core::fmt;
+    use core::ops::Range;
     // imports for intra doc links
     #[cfg(doc)]
     use crate::registers::{
@@ -181,6 +379,77 @@ mod x86_64 {
     };
     use core::arch::asm;
 
+    /// Implements `read`, `read_raw`, `write`, `write_raw`, and `update` for a model specific
+    /// register type that consists of a bitflags type. `write` and `update` preserve the bits
+    /// that are not covered by the flags type. The doc comment is used as the safety section of
+    /// the unsafe methods.
+    macro_rules! flags_msr_impl {
+        ($reg:ident, $flags:ident, $name:literal, $(#[doc = $safety:literal])+) => {
+            impl $reg {
+                #[doc = concat!("Read the current ", $name, " flags.")]
+                #[inline]
+                pub fn read() -> $flags {
+                    $flags::from_bits_truncate(Self::read_raw())
+                }
+
+                #[doc = concat!("Read the current raw ", $name, " flags.")]
+                #[inline]
+                pub fn read_raw() -> u64 {
+                    unsafe { Self::MSR.read() }
+                }
+
+                #[doc = concat!("Write the ", $name, " flags, preserving reserved values.")]
+                ///
+                /// ## Safety
+                ///
+                $(#[doc = $safety])+
+                #[inline]
+                pub unsafe fn write(flags: $flags) {
+                    let new_value =
+                        preserve_reserved(Self::read_raw(), $flags::all().bits(), flags.bits());
+
+                    unsafe {
+                        Self::write_raw(new_value);
+                    }
+                }
+
+                #[doc = concat!("Write the ", $name, " flags.")]
+                ///
+                /// Does not preserve any bits, including reserved fields.
+                ///
+                /// ## Safety
+                ///
+                $(#[doc = $safety])+
+                #[inline]
+                pub unsafe fn write_raw(flags: u64) {
+                    let mut msr = Self::MSR;
+                    unsafe {
+                        msr.write(flags);
+                    }
+                }
+
+                #[doc = concat!("Update the ", $name, " flags.")]
+                ///
+                /// Preserves the value of reserved fields.
+                ///
+                /// ## Safety
+                ///
+                $(#[doc = $safety])+
+                #[inline]
+                pub unsafe fn update<F>(f: F)
+                where
+                    F: FnOnce(&mut $flags),
+                {
+                    let mut flags = Self::read();
+                    f(&mut flags);
+                    unsafe {
+                        Self::write(flags);
+                    }
+                }
+            }
+        };
+    }
+
     impl Msr {
         /// Read 64 bits msr register.
         ///
@@ -222,77 +491,89 @@ mod x86_64 {
                 );
             }
         }
-    }
-
-    impl Efer {
-        /// Read the current EFER flags.
-        #[inline]
-        pub fn read() -> EferFlags {
-            EferFlags::from_bits_truncate(Self::read_raw())
-        }
-
-        /// Read the current raw EFER flags.
-        #[inline]
-        pub fn read_raw() -> u64 {
-            unsafe { Self::MSR.read() }
-        }
 
-        /// Write the EFER flags, preserving reserved values.
+        /// Write 64 bits to msr register using the non-serializing `wrmsrns` instruction.
         ///
-        /// Preserves the value of reserved fields.
+        /// Unlike [`write`](Self::write), `wrmsrns` doesn't wait for preceding instructions to
+        /// complete and doesn't prevent later instructions from being executed before the write,
+        /// which makes it faster for registers that are written frequently, e.g. the
+        /// [`GsBase`] on context switches. If the write must be ordered with respect to other
+        /// instructions, the caller has to add the necessary fences or serializing instructions.
         ///
         /// ## Safety
         ///
-        /// Unsafe because it's possible to break memory
-        /// safety with wrong flags, e.g. by disabling long mode.
+        /// The caller must ensure that the CPU supports `wrmsrns`, e.g. by checking
+        /// [`cpu::supports_wrmsrns`](crate::instructions::cpu::supports_wrmsrns) once and
+        /// falling back to [`write`](Self::write) otherwise. Executing it on a CPU without
+        /// support raises an invalid opcode exception.
+        ///
+        /// The caller must also ensure that this write operation has no unsafe side
+        /// effects, including ones caused by the missing serialization.
         #[inline]
-        pub unsafe fn write(flags: EferFlags) {
-            let old_value = Self::read_raw();
-            let reserved = old_value & !(EferFlags::all().bits());
-            let new_value = reserved | flags.bits();
+        pub unsafe fn write_nonserializing(&mut self, value: u64) {
+            let low = value as u32;
+            let high = (value >> 32) as u32;
 
             unsafe {
-                Self::write_raw(new_value);
+                asm!(
+                    // `wrmsrns` is encoded manually, since older assemblers don't know it.
+                    ".byte 0x0f, 0x01, 0xc6",
+                    in("ecx") self.0,
+                    in("eax") low, in("edx") high,
+                    options(nostack, preserves_flags),
+                );
             }
         }
 
-        /// Write the EFER flags.
+        /// Read the bits in the given range of the msr register, shifted to the least
+        /// significant bits.
+        ///
+        /// For example, `read_bits(12..36)` returns bits 12 through 35 of the register.
         ///
-        /// Does not preserve any bits, including reserved fields.
+        /// ## Panics
+        ///
+        /// Panics if the range is empty or doesn't fit into 64 bits.
         ///
         /// ## Safety
         ///
-        /// Unsafe because it's possible to
-        /// break memory safety with wrong flags, e.g. by disabling long mode.
+        /// The caller must ensure that this read operation has no unsafe side
+        /// effects.
         #[inline]
-        pub unsafe fn write_raw(flags: u64) {
-            let mut msr = Self::MSR;
-            unsafe {
-                msr.write(flags);
-            }
+        pub unsafe fn read_bits(&self, range: Range<u32>) -> u64 {
+            let range = range.start as usize..range.end as usize;
+            unsafe { self.read() }.get_bits(range)
         }
 
-        /// Update EFER flags.
+        /// Write `value` to the bits in the given range of the msr register, leaving all other
+        /// bits unchanged.
         ///
-        /// Preserves the value of reserved fields.
+        /// This is a read-modify-write operation, so it is not atomic with respect to other
+        /// writes to the register.
+        ///
+        /// ## Panics
+        ///
+        /// Panics if the range is empty, doesn't fit into 64 bits, or if `value` doesn't fit
+        /// into the range.
         ///
         /// ## Safety
         ///
-        /// Unsafe because it's possible to break memory
-        /// safety with wrong flags, e.g. by disabling long mode.
+        /// The caller must ensure that this read-modify-write operation has no unsafe side
+        /// effects.
         #[inline]
-        pub unsafe fn update<F>(f: F)
-        where
-            F: FnOnce(&mut EferFlags),
-        {
-            let mut flags = Self::read();
-            f(&mut flags);
-            unsafe {
-                Self::write(flags);
-            }
+        pub unsafe fn write_bits(&mut self, range: Range<u32>, value: u64) {
+            let range = range.start as usize..range.end as usize;
+            let mut msr_value = unsafe { self.read() };
+            msr_value.set_bits(range, value);
+            unsafe { self.write(msr_value) };
         }
     }
 
+    flags_msr_impl! {
+        Efer, EferFlags, "EFER",
+        /// Unsafe because it's possible to break memory
+        /// safety with wrong flags, e.g. by disabling long mode.
+    }
+
     impl FsBase {
         /// Read the current FsBase register.
         ///
@@ -636,4 +917,168 @@ mod x86_64 {
             Self::write(flags, legacy_bitmap);
         }
     }
+
+    // Not implemented through `flags_msr_impl!`, because writing the register after it has
+    // been locked causes a `#GP`, so `write` and `update` check the lock bit and return a
+    // `Result` instead.
+    impl FeatureControl {
+        /// Read the current IA32_FEATURE_CONTROL flags.
+        #[inline]
+        pub fn read() -> FeatureControlFlags {
+            FeatureControlFlags::from_bits_truncate(Self::read_raw())
+        }
+
+        /// Read the current raw IA32_FEATURE_CONTROL flags.
+        #[inline]
+        pub fn read_raw() -> u64 {
+            unsafe { Self::MSR.read() }
+        }
+
+        /// Returns whether the register is locked and can no longer be written.
+        #[inline]
+        pub fn is_locked() -> bool {
+            Self::read().contains(FeatureControlFlags::LOCKED)
+        }
+
+        /// Write the IA32_FEATURE_CONTROL flags, preserving reserved values.
+        ///
+        /// Writing the register after it has been locked causes a general
+        /// protection fault, so this function returns
+        /// [`FeatureControlLocked`] without writing if the lock bit is already
+        /// set. Setting [`FeatureControlFlags::LOCKED`] in `flags` locks the
+        /// register until the next reset.
+        ///
+        /// ## Safety
+        ///
+        /// The caller must ensure that all set flags are supported by the
+        /// processor, otherwise the write causes a general protection fault.
+        #[inline]
+        pub unsafe fn write(flags: FeatureControlFlags) -> Result<(), FeatureControlLocked> {
+            let old_value = Self::read_raw();
+            if old_value & FeatureControlFlags::LOCKED.bits() != 0 {
+                return Err(FeatureControlLocked);
+            }
+            let new_value =
+                preserve_reserved(old_value, FeatureControlFlags::all().bits(), flags.bits());
+
+            let mut msr = Self::MSR;
+            unsafe {
+                msr.write(new_value);
+            }
+            Ok(())
+        }
+
+        /// Update the IA32_FEATURE_CONTROL flags.
+        ///
+        /// Preserves the value of reserved fields. Returns
+        /// [`FeatureControlLocked`] without writing if the register is
+        /// already locked.
+        ///
+        /// ## Safety
+        ///
+        /// The caller must ensure that all set flags are supported by the
+        /// processor, otherwise the write causes a general protection fault.
+        #[inline]
+        pub unsafe fn update<F>(f: F) -> Result<(), FeatureControlLocked>
+        where
+            F: FnOnce(&mut FeatureControlFlags),
+        {
+            let mut flags = Self::read();
+            f(&mut flags);
+            unsafe { Self::write(flags) }
+        }
+    }
+    flags_msr_impl! {
+        MiscEnable, MiscEnableFlags, "IA32_MISC_ENABLE",
+        /// Unsafe because changing the read-only flags or reserved bits
+        /// causes a general protection fault and because some flags affect
+        /// the behavior of other features, e.g. [`XD_BIT_DISABLE`](MiscEnableFlags::XD_BIT_DISABLE)
+        /// disables the no-execute protection of all page tables.
+    }
+
+    flags_msr_impl! {
+        SpecCtrl, SpecCtrlFlags, "IA32_SPEC_CTRL",
+        /// Unsafe because the register doesn't exist on all processors and
+        /// because setting an unsupported flag causes a general protection
+        /// fault. The caller must check the `cpuid` feature bits first.
+    }
+
+    impl PredCmd {
+        /// Write the given commands to IA32_PRED_CMD.
+        ///
+        /// The register is write-only, so there is no corresponding `read`.
+        ///
+        /// ## Safety
+        ///
+        /// Unsafe because the register doesn't exist on all processors and
+        /// because writing an unsupported command causes a general protection
+        /// fault. The caller must check the `cpuid` feature bits first.
+        #[inline]
+        pub unsafe fn write(commands: PredCmdFlags) {
+            let mut msr = Self::MSR;
+            unsafe {
+                msr.write(commands.bits());
+            }
+        }
+
+        /// Issue an indirect branch prediction barrier (IBPB).
+        ///
+        /// This is typically done when switching between address spaces that don't trust
+        /// each other, so that one can't steer the indirect branch predictions of the other.
+        ///
+        /// ## Safety
+        ///
+        /// Unsafe because the register doesn't exist on all processors. The
+        /// caller must check that IBPB is supported, see [`PredCmd`].
+        #[inline]
+        pub unsafe fn ibpb() {
+            unsafe { Self::write(PredCmdFlags::IBPB) }
+        }
+    }
+
+    /// Combines the reserved bits of `old_value`, i.e. the bits outside of `valid_bits`, with
+    /// the given `flags`.
+    #[inline]
+    const fn preserve_reserved(old_value: u64, valid_bits: u64, flags: u64) -> u64 {
+        (old_value & !valid_bits) | flags
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn speculation_control_layout() {
+            assert_eq!(SpecCtrlFlags::IBRS.bits(), 1);
+            assert_eq!(SpecCtrlFlags::STIBP.bits(), 1 << 1);
+            assert_eq!(SpecCtrlFlags::SSBD.bits(), 1 << 2);
+            assert_eq!(PredCmdFlags::IBPB.bits(), 1);
+            assert_eq!(SpecCtrl::MSR.0, 0x48);
+            assert_eq!(PredCmd::MSR.0, 0x49);
+        }
+
+        #[test]
+        fn write_preserves_reserved_bits() {
+            let valid = EferFlags::all().bits();
+            let reserved = 1 << 20 | 1 << 63;
+            let old = reserved | EferFlags::LONG_MODE_ENABLE.bits();
+            let flags = EferFlags::SYSTEM_CALL_EXTENSIONS | EferFlags::NO_EXECUTE_ENABLE;
+            assert_eq!(
+                preserve_reserved(old, valid, flags.bits()),
+                reserved | flags.bits()
+            );
+
+            let valid = SpecCtrlFlags::all().bits();
+            assert_eq!(valid, 0b111);
+            let old = 1 << 10 | SpecCtrlFlags::IBRS.bits();
+            assert_eq!(
+                preserve_reserved(old, valid, SpecCtrlFlags::SSBD.bits()),
+                1 << 10 | 1 << 2
+            );
+
+            let valid = MiscEnableFlags::all().bits();
+            let old = !valid | MiscEnableFlags::FAST_STRINGS.bits();
+            assert_eq!(preserve_reserved(old, valid, 0), !valid);
+        }
+    }
 }
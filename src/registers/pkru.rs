@@ -0,0 +1,200 @@
+//! Functions to read and write the protection key rights register (PKRU).
+
+/// The number of protection keys.
+pub const PROTECTION_KEY_COUNT: u8 = 16;
+
+/// The protection key rights register for user-mode pages.
+///
+/// Every user-mode page is tagged with one of 16 protection keys in bits 59..63 of its page
+/// table entry. PKRU holds two bits per key: the access-disable bit `2 * key` and the
+/// write-disable bit `2 * key + 1`. The register is only accessible when
+/// [`Cr4Flags::PROTECTION_KEY_USER`](crate::registers::control::Cr4Flags::PROTECTION_KEY_USER)
+/// is set.
+#[derive(Debug)]
+pub struct PkRu;
+
+/// A protection key, i.e. an index into the [`PkRu`] register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProtectionKey(u8);
+
+impl ProtectionKey {
+    /// Creates a new protection key from the given `u8`. Panics if the given value is >=16.
+    #[inline]
+    pub const fn new(key: u8) -> Self {
+        assert!(key < PROTECTION_KEY_COUNT);
+        Self(key)
+    }
+
+    /// Creates a new protection key from the given `u8`. Throws away bits if the value is >=16.
+    #[inline]
+    pub const fn new_truncate(key: u8) -> Self {
+        Self(key % PROTECTION_KEY_COUNT)
+    }
+
+    /// Returns the mask of the access-disable bit of this key in PKRU.
+    #[inline]
+    pub const fn access_disable_mask(self) -> u32 {
+        1 << (2 * self.0)
+    }
+
+    /// Returns the mask of the write-disable bit of this key in PKRU.
+    #[inline]
+    pub const fn write_disable_mask(self) -> u32 {
+        1 << (2 * self.0 + 1)
+    }
+
+    /// Returns whether data accesses to pages with this key are disabled in the given PKRU value.
+    #[inline]
+    pub const fn is_access_disabled(self, pkru: u32) -> bool {
+        pkru & self.access_disable_mask() != 0
+    }
+
+    /// Returns whether writes to pages with this key are disabled in the given PKRU value.
+    #[inline]
+    pub const fn is_write_disabled(self, pkru: u32) -> bool {
+        pkru & self.write_disable_mask() != 0
+    }
+
+    /// Returns the given PKRU value with the access-disable bit of this key set to `disabled`.
+    #[inline]
+    pub const fn with_access_disabled(self, pkru: u32, disabled: bool) -> u32 {
+        if disabled {
+            pkru | self.access_disable_mask()
+        } else {
+            pkru & !self.access_disable_mask()
+        }
+    }
+
+    /// Returns the given PKRU value with the write-disable bit of this key set to `disabled`.
+    #[inline]
+    pub const fn with_write_disabled(self, pkru: u32, disabled: bool) -> u32 {
+        if disabled {
+            pkru | self.write_disable_mask()
+        } else {
+            pkru & !self.write_disable_mask()
+        }
+    }
+}
+
+impl From<ProtectionKey> for u8 {
+    #[inline]
+    fn from(key: ProtectionKey) -> Self {
+        key.0
+    }
+}
+
+#[cfg(all(feature = "instructions", target_arch = "x86_64"))]
+mod x86_64 {
+    use super::*;
+    use core::arch::asm;
+
+    impl PkRu {
+        /// Reads the current value of PKRU using `rdpkru`.
+        ///
+        /// Note that this raises an invalid opcode exception (`#UD`) if
+        /// `Cr4Flags::PROTECTION_KEY_USER` is not set.
+        #[inline]
+        pub fn read() -> u32 {
+            let value: u32;
+            unsafe {
+                asm!(
+                    "rdpkru",
+                    in("ecx") 0,
+                    out("eax") value,
+                    out("edx") _,
+                    options(nomem, nostack, preserves_flags),
+                );
+            }
+            value
+        }
+
+        /// Writes the given value to PKRU using `wrpkru`.
+        ///
+        /// `wrpkru` requires ECX and EDX to be zero, which this function ensures.
+        ///
+        /// ## Safety
+        ///
+        /// This function is unsafe because the caller must ensure that
+        /// `Cr4Flags::PROTECTION_KEY_USER` is set, otherwise an invalid opcode exception (`#UD`)
+        /// is raised. Disabling access for a key makes all user-mode pages tagged with it
+        /// inaccessible to the current thread, which can break memory safety.
+        #[inline]
+        pub unsafe fn write(value: u32) {
+            unsafe {
+                asm!(
+                    "wrpkru",
+                    in("eax") value,
+                    in("ecx") 0,
+                    in("edx") 0,
+                    options(nostack, preserves_flags),
+                );
+            }
+        }
+
+        /// Updates PKRU with the given closure.
+        ///
+        /// ## Safety
+        ///
+        /// See [`PkRu::write`].
+        #[inline]
+        pub unsafe fn update<F>(f: F)
+        where
+            F: FnOnce(&mut u32),
+        {
+            let mut value = Self::read();
+            f(&mut value);
+            unsafe {
+                Self::write(value);
+            }
+        }
+
+        /// Sets or clears the access-disable bit of the given key.
+        ///
+        /// ## Safety
+        ///
+        /// See [`PkRu::write`].
+        #[inline]
+        pub unsafe fn set_access_disabled(key: ProtectionKey, disabled: bool) {
+            unsafe { Self::update(|value| *value = key.with_access_disabled(*value, disabled)) }
+        }
+
+        /// Sets or clears the write-disable bit of the given key.
+        ///
+        /// ## Safety
+        ///
+        /// See [`PkRu::write`].
+        #[inline]
+        pub unsafe fn set_write_disabled(key: ProtectionKey, disabled: bool) {
+            unsafe { Self::update(|value| *value = key.with_write_disabled(*value, disabled)) }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_bits() {
+        let key = ProtectionKey::new(5);
+        assert_eq!(key.access_disable_mask(), 1 << 10);
+        assert_eq!(key.write_disable_mask(), 1 << 11);
+
+        let pkru = key.with_write_disabled(key.with_access_disabled(0, true), true);
+        assert_eq!(pkru, 0b11 << 10);
+        assert!(key.is_access_disabled(pkru));
+        assert!(key.is_write_disabled(pkru));
+        assert!(!ProtectionKey::new(4).is_access_disabled(pkru));
+
+        let pkru = key.with_access_disabled(pkru, false);
+        assert_eq!(pkru, 1 << 11);
+        assert_eq!(ProtectionKey::new(15).write_disable_mask(), 1 << 31);
+        assert_eq!(u8::from(ProtectionKey::new_truncate(17)), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn key_out_of_range() {
+        ProtectionKey::new(16);
+    }
+}
@@ -3,6 +3,7 @@
 #[cfg(all(feature = "instructions", target_arch = "x86_64"))]
 pub use self::x86_64::*;
 
+use crate::PrivilegeLevel;
 use bitflags::bitflags;
 
 bitflags! {
@@ -64,6 +65,28 @@ bitflags! {
     }
 }
 
+impl RFlags {
+    /// Bit position of the two-bit I/O Privilege Level field.
+    const IOPL_SHIFT: u64 = 12;
+
+    /// Returns the I/O Privilege Level stored in bits 12 and 13.
+    ///
+    /// Code running with a CPL numerically less than or equal to the IOPL is allowed to
+    /// execute I/O instructions without consulting the I/O permission bitmap of the TSS.
+    #[inline]
+    pub const fn iopl(&self) -> PrivilegeLevel {
+        let bits = self.bits() & (Self::IOPL_HIGH.bits() | Self::IOPL_LOW.bits());
+        PrivilegeLevel::from_u16((bits >> Self::IOPL_SHIFT) as u16)
+    }
+
+    /// Sets the I/O Privilege Level in bits 12 and 13, leaving all other flags unchanged.
+    #[inline]
+    pub fn set_iopl(&mut self, iopl: PrivilegeLevel) {
+        self.remove(Self::IOPL_HIGH | Self::IOPL_LOW);
+        *self |= Self::from_bits_truncate((iopl as u64) << Self::IOPL_SHIFT);
+    }
+}
+
 #[cfg(all(feature = "instructions", target_arch = "x86_64"))]
 mod x86_64 {
     use super::*;
@@ -156,3 +179,31 @@ mod x86_64 {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iopl() {
+        let mut flags = RFlags::INTERRUPT_FLAG | RFlags::NESTED_TASK;
+        assert_eq!(flags.iopl(), PrivilegeLevel::Ring0);
+
+        flags.set_iopl(PrivilegeLevel::Ring3);
+        assert_eq!(flags.bits(), (1 << 14) | (0b11 << 12) | (1 << 9));
+        assert_eq!(flags.iopl(), PrivilegeLevel::Ring3);
+
+        flags.set_iopl(PrivilegeLevel::Ring2);
+        assert!(flags.contains(RFlags::IOPL_HIGH));
+        assert!(!flags.contains(RFlags::IOPL_LOW));
+        assert_eq!(flags.iopl(), PrivilegeLevel::Ring2);
+
+        flags.set_iopl(PrivilegeLevel::Ring1);
+        assert!(!flags.contains(RFlags::IOPL_HIGH));
+        assert!(flags.contains(RFlags::IOPL_LOW));
+        assert_eq!(flags.iopl(), PrivilegeLevel::Ring1);
+
+        flags.set_iopl(PrivilegeLevel::Ring0);
+        assert_eq!(flags, RFlags::INTERRUPT_FLAG | RFlags::NESTED_TASK);
+    }
+}
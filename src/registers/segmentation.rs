@@ -23,6 +23,12 @@ pub trait Segment {
     /// Reload the segment register. Depending on the segment, this may also
     /// reconfigure the corresponding segment.
     ///
+    /// For all segments except [`CS`], this is a plain `mov` to the segment
+    /// register. `CS` can't be the target of a `mov`, so it is reloaded
+    /// together with the instruction pointer through a far return, which
+    /// continues right after the call. To continue somewhere else instead,
+    /// use `CS::reload`.
+    ///
     /// ## Safety
     ///
     /// This function is unsafe because the caller must ensure that `sel`
@@ -45,12 +51,18 @@ pub trait Segment64: Segment {
     /// ## Exceptions
     ///
     /// If [`CR4.FSGSBASE`][Cr4Flags::FSGSBASE] is not set, this instruction will throw a `#UD`.
+    /// In debug builds, this function panics instead when running in ring 0.
     fn read_base() -> VirtAddr;
     /// Writes the segment base address
     ///
     /// ## Exceptions
     ///
     /// If [`CR4.FSGSBASE`][Cr4Flags::FSGSBASE] is not set, this instruction will throw a `#UD`.
+    /// In debug builds, this function panics instead when running in ring 0.
+    ///
+    /// Writing a non-canonical base causes a `#GP`, so this function panics instead if `base`
+    /// is not canonical. This can only happen if it was created through
+    /// [`VirtAddr::new_unsafe`].
     ///
     /// ## Safety
     ///
@@ -168,3 +180,24 @@ pub struct FS;
 /// base often points to a per-cpu kernel data structure.
 #[derive(Debug)]
 pub struct GS;
+
+/// The selectors of all six segment registers.
+///
+/// This can be used to save the segment registers, e.g. during a context
+/// switch. Note that only the selectors are contained; the [`FS`] and [`GS`]
+/// base addresses have to be saved separately through [`Segment64`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SegmentRegisters {
+    /// The selector in the [`CS`] register.
+    pub cs: SegmentSelector,
+    /// The selector in the [`SS`] register.
+    pub ss: SegmentSelector,
+    /// The selector in the [`DS`] register.
+    pub ds: SegmentSelector,
+    /// The selector in the [`ES`] register.
+    pub es: SegmentSelector,
+    /// The selector in the [`FS`] register.
+    pub fs: SegmentSelector,
+    /// The selector in the [`GS`] register.
+    pub gs: SegmentSelector,
+}
@@ -1,4 +1,9 @@
 //! Access to various extended system registers
+
+#[cfg(all(feature = "instructions", target_arch = "x86_64"))]
+pub use super::bootstrap::enable_avx;
+pub use super::bootstrap::AvxNotSupported;
+
 use bitflags::bitflags;
 
 /// Extended feature enable mask register
@@ -84,6 +89,9 @@ mod x86_64 {
         ///
         /// ## Safety
         ///
+        /// Accessing XCR0 causes an invalid opcode exception (`#UD`) if
+        /// [`Cr4Flags::OSXSAVE`](crate::registers::control::Cr4Flags::OSXSAVE) is not set.
+        ///
         /// This function is unsafe because it's possible to
         /// enable features that are not supported by the architecture.
         #[inline]
@@ -129,6 +137,9 @@ mod x86_64 {
         ///
         /// ## Safety
         ///
+        /// Accessing XCR0 causes an invalid opcode exception (`#UD`) if
+        /// [`Cr4Flags::OSXSAVE`](crate::registers::control::Cr4Flags::OSXSAVE) is not set.
+        ///
         /// This function is unsafe because it's possible to
         /// enable features that are not supported by the architecture
         #[inline]
@@ -153,6 +164,9 @@ mod x86_64 {
         ///
         /// ## Safety
         ///
+        /// Accessing XCR0 causes an invalid opcode exception (`#UD`) if
+        /// [`Cr4Flags::OSXSAVE`](crate::registers::control::Cr4Flags::OSXSAVE) is not set.
+        ///
         /// This function is unsafe because it's possible to
         /// enable features that are not supported by the architecture.
         #[inline]
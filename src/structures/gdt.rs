@@ -214,6 +214,107 @@ impl<const MAX: usize> GlobalDescriptorTable<MAX> {
         SegmentSelector::new(index as u16, entry.dpl())
     }
 
+    /// Appends the 64-bit kernel code and data segments, in this order, returning their
+    /// selectors.
+    ///
+    /// The data segment directly follows the code segment, as required by the `syscall`
+    /// instruction. Together with the selectors returned by
+    /// [`append_user_segments`](Self::append_user_segments), the selectors can be passed to
+    /// [`Star::write`](crate::registers::model_specific::Star::write) as is.
+    ///
+    /// Panics if the GDT doesn't have two free entries.
+    #[cfg_attr(
+        not(all(feature = "instructions", target_arch = "x86_64")),
+        allow(rustdoc::broken_intra_doc_links)
+    )]
+    #[inline]
+    pub fn append_kernel_segments(&mut self) -> KernelSelectors {
+        KernelSelectors {
+            code: self.append(Descriptor::kernel_code_segment()),
+            data: self.append(Descriptor::kernel_data_segment()),
+        }
+    }
+
+    /// Appends the user data and 64-bit user code segments, in this order, returning their
+    /// selectors.
+    ///
+    /// The code segment directly follows the data segment, as required by the 64-bit form of
+    /// the `sysret` instruction.
+    ///
+    /// Panics if the GDT doesn't have two free entries.
+    ///
+    /// # Example
+    /// ```
+    /// use x86_64::structures::gdt::GlobalDescriptorTable;
+    /// use x86_64::PrivilegeLevel;
+    ///
+    /// let mut gdt = GlobalDescriptorTable::new();
+    /// let kernel = gdt.append_kernel_segments();
+    /// let user = gdt.append_user_segments();
+    /// assert_eq!((kernel.code.index(), kernel.data.index()), (1, 2));
+    /// assert_eq!((user.data.index(), user.code.index()), (3, 4));
+    /// assert_eq!(user.code.rpl(), PrivilegeLevel::Ring3);
+    /// ```
+    #[inline]
+    pub fn append_user_segments(&mut self) -> UserSelectors {
+        let data = self.append(Descriptor::user_data_segment());
+        let code = self.append(Descriptor::user_code_segment());
+        UserSelectors { code, data }
+    }
+
+    /// Appends a TSS descriptor for the given TSS with the given Descriptor
+    /// Privilege Level (DPL) to the GDT, returning the segment selector.
+    ///
+    /// A TSS descriptor is a system segment descriptor, so it occupies two
+    /// [`Entry`]s. The returned selector points to the first of them, and the
+    /// next appended descriptor is placed after the second one.
+    ///
+    /// The returned selector can be passed to
+    /// [`load_tss`](crate::instructions::tables::load_tss) after the GDT has
+    /// been loaded, or to
+    /// [`load_tss_with_gdt`](crate::instructions::tables::load_tss_with_gdt),
+    /// which does both steps at once. See [`Descriptor::tss_segment`] for
+    /// more information on TSS descriptors.
+    ///
+    /// Panics if the GDT doesn't have two free entries.
+    ///
+    /// # Example
+    /// ```
+    /// use x86_64::structures::gdt::{Descriptor, GlobalDescriptorTable};
+    /// use x86_64::structures::tss::TaskStateSegment;
+    /// use x86_64::PrivilegeLevel;
+    ///
+    /// static TSS: TaskStateSegment = TaskStateSegment::new();
+    ///
+    /// let mut gdt = GlobalDescriptorTable::new();
+    /// gdt.append(Descriptor::kernel_code_segment());
+    /// let tss_selector = gdt.append_tss(&TSS, PrivilegeLevel::Ring0);
+    /// assert_eq!(tss_selector.index(), 2);
+    /// let data_selector = gdt.append(Descriptor::kernel_data_segment());
+    /// assert_eq!(data_selector.index(), 4);
+    ///
+    /// // Load the GDT, then load the TSS using `tss_selector`
+    /// ```
+    #[cfg_attr(
+        not(all(feature = "instructions", target_arch = "x86_64")),
+        allow(rustdoc::broken_intra_doc_links)
+    )]
+    #[inline]
+    pub fn append_tss(
+        &mut self,
+        tss: &'static TaskStateSegment,
+        dpl: PrivilegeLevel,
+    ) -> SegmentSelector {
+        let descriptor = match Descriptor::tss_segment(tss) {
+            Descriptor::SystemSegment(mut low, high) => {
+                low.set_bits(45..47, dpl as u64);
+                Descriptor::SystemSegment(low, high)
+            }
+            Descriptor::UserSegment(_) => unreachable!(),
+        };
+        self.append(descriptor)
+    }
+
     /// Loads the GDT in the CPU using the `lgdt` instruction. This does **not** alter any of the
     /// segment registers; you **must** (re)load them yourself using [the appropriate
     /// functions](crate::instructions::segmentation):
@@ -245,6 +346,60 @@ impl<const MAX: usize> GlobalDescriptorTable<MAX> {
         }
     }
 
+    /// Returns whether the TSS descriptor referenced by the given selector is
+    /// marked as busy.
+    ///
+    /// The CPU sets the busy bit when the TSS is loaded through
+    /// [`load_tss`](crate::instructions::tables::load_tss). Loading a busy TSS
+    /// again results in a `#GP` exception.
+    ///
+    /// Panics if the selector does not point to a TSS descriptor in this GDT.
+    #[cfg_attr(
+        not(all(feature = "instructions", target_arch = "x86_64")),
+        allow(rustdoc::broken_intra_doc_links)
+    )]
+    #[inline]
+    pub fn is_tss_busy(&self, selector: SegmentSelector) -> bool {
+        let raw = self.table[self.tss_index(selector)].raw();
+        raw.get_bits(40..44) == TSS_BUSY
+    }
+
+    /// Clears the busy bit of the TSS descriptor referenced by the given
+    /// selector, so that the TSS can be loaded again.
+    ///
+    /// This is needed before reloading a TSS that was already loaded, for
+    /// example after switching to another TSS or when re-entering the kernel
+    /// after a warm reset. See
+    /// [`reload_tss`](crate::instructions::tables::reload_tss) for a function
+    /// that combines this with loading the TSS.
+    ///
+    /// Note that the busy bit must not be cleared while the TSS is loaded on
+    /// another CPU.
+    ///
+    /// Panics if the selector does not point to a TSS descriptor in this GDT.
+    #[cfg(all(feature = "instructions", target_arch = "x86_64"))]
+    #[inline]
+    pub fn clear_tss_busy(&self, selector: SegmentSelector) {
+        let index = self.tss_index(selector);
+        self.table[index].0.fetch_and(!(1 << 41), Ordering::SeqCst);
+    }
+
+    /// Returns the table index of the TSS descriptor referenced by the given
+    /// selector, panicking if there is no TSS descriptor at that index.
+    fn tss_index(&self, selector: SegmentSelector) -> usize {
+        let index = usize::from(selector.index());
+        assert!(
+            index + 1 < self.len,
+            "selector does not point to a system segment in the GDT"
+        );
+        let ty = self.table[index].raw().get_bits(40..45);
+        assert!(
+            ty == TSS_AVAILABLE || ty == TSS_BUSY,
+            "selector does not point to a TSS descriptor"
+        );
+        index
+    }
+
     #[inline]
     #[rustversion::attr(since(1.83), const)]
     fn push(&mut self, value: u64) -> usize {
@@ -273,6 +428,11 @@ impl<const MAX: usize> GlobalDescriptorTable<MAX> {
     }
 }
 
+/// Descriptor type of an available 64-bit TSS.
+const TSS_AVAILABLE: u64 = 0b1001;
+/// Descriptor type of a busy 64-bit TSS.
+const TSS_BUSY: u64 = 0b1011;
+
 /// A 64-bit mode segment descriptor.
 ///
 /// Segmentation is no longer supported in 64-bit mode, so most of the descriptor
@@ -288,6 +448,26 @@ pub enum Descriptor {
     SystemSegment(u64, u64),
 }
 
+/// The selectors of the kernel segments appended by
+/// [`GlobalDescriptorTable::append_kernel_segments`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KernelSelectors {
+    /// The selector of the 64-bit kernel code segment.
+    pub code: SegmentSelector,
+    /// The selector of the kernel data segment.
+    pub data: SegmentSelector,
+}
+
+/// The selectors of the user segments appended by
+/// [`GlobalDescriptorTable::append_user_segments`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UserSelectors {
+    /// The selector of the 64-bit user code segment, with an RPL of 3.
+    pub code: SegmentSelector,
+    /// The selector of the user data segment, with an RPL of 3.
+    pub data: SegmentSelector,
+}
+
 bitflags! {
     /// Flags for a GDT descriptor. Not all flags are valid for all descriptor types.
     #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
@@ -389,32 +569,84 @@ impl Descriptor {
         PrivilegeLevel::from_u16(dpl as u16)
     }
 
+    /// Decodes the fields of a raw 8-byte descriptor table entry.
+    ///
+    /// This is the counterpart to the descriptor constructors and is useful for
+    /// inspecting a GDT that was set up by other software, e.g. firmware or a
+    /// bootloader (see [`GlobalDescriptorTable::entries`] and [`Entry::raw`]).
+    ///
+    /// ```
+    /// use x86_64::structures::gdt::{Descriptor, DescriptorFlags};
+    /// use x86_64::PrivilegeLevel;
+    ///
+    /// let info = Descriptor::decode(DescriptorFlags::USER_CODE64.bits());
+    /// assert!(info.present && info.user_segment && info.long_mode);
+    /// assert_eq!(info.dpl, PrivilegeLevel::Ring3);
+    /// ```
+    #[inline]
+    pub const fn decode(raw: u64) -> DescriptorInfo {
+        let base = ((raw >> 16) & 0xff_ffff) | (((raw >> 56) & 0xff) << 24);
+        let limit = (raw & 0xffff) | (((raw >> 48) & 0xf) << 16);
+        let flags = DescriptorFlags::from_bits_retain(raw);
+        DescriptorInfo {
+            base: base as u32,
+            limit: limit as u32,
+            segment_type: ((raw >> 40) & 0xf) as u8,
+            user_segment: flags.contains(DescriptorFlags::USER_SEGMENT),
+            dpl: PrivilegeLevel::from_u16(((raw >> 45) & 0b11) as u16),
+            present: flags.contains(DescriptorFlags::PRESENT),
+            long_mode: flags.contains(DescriptorFlags::LONG_MODE),
+            default_size: flags.contains(DescriptorFlags::DEFAULT_SIZE),
+            granularity: flags.contains(DescriptorFlags::GRANULARITY),
+        }
+    }
+
+    /// Creates a segment descriptor for a 64-bit code segment with the given
+    /// Descriptor Privilege Level (DPL).
+    ///
+    /// Apart from the DPL, the descriptor is identical to
+    /// [`kernel_code_segment`](Self::kernel_code_segment).
+    #[inline]
+    pub const fn code_segment(dpl: PrivilegeLevel) -> Descriptor {
+        Descriptor::UserSegment(DescriptorFlags::KERNEL_CODE64.bits() | (dpl as u64) << 45)
+    }
+
+    /// Creates a segment descriptor for a data segment (32-bit or 64-bit) with
+    /// the given Descriptor Privilege Level (DPL).
+    ///
+    /// Apart from the DPL, the descriptor is identical to
+    /// [`kernel_data_segment`](Self::kernel_data_segment).
+    #[inline]
+    pub const fn data_segment(dpl: PrivilegeLevel) -> Descriptor {
+        Descriptor::UserSegment(DescriptorFlags::KERNEL_DATA.bits() | (dpl as u64) << 45)
+    }
+
     /// Creates a segment descriptor for a 64-bit kernel code segment. Suitable
     /// for use with `syscall` or 64-bit `sysenter`.
     #[inline]
     pub const fn kernel_code_segment() -> Descriptor {
-        Descriptor::UserSegment(DescriptorFlags::KERNEL_CODE64.bits())
+        Self::code_segment(PrivilegeLevel::Ring0)
     }
 
     /// Creates a segment descriptor for a kernel data segment (32-bit or
     /// 64-bit). Suitable for use with `syscall` or `sysenter`.
     #[inline]
     pub const fn kernel_data_segment() -> Descriptor {
-        Descriptor::UserSegment(DescriptorFlags::KERNEL_DATA.bits())
+        Self::data_segment(PrivilegeLevel::Ring0)
     }
 
     /// Creates a segment descriptor for a ring 3 data segment (32-bit or
     /// 64-bit). Suitable for use with `sysret` or `sysexit`.
     #[inline]
     pub const fn user_data_segment() -> Descriptor {
-        Descriptor::UserSegment(DescriptorFlags::USER_DATA.bits())
+        Self::data_segment(PrivilegeLevel::Ring3)
     }
 
     /// Creates a segment descriptor for a 64-bit ring 3 code segment. Suitable
     /// for use with `sysret` or `sysexit`.
     #[inline]
     pub const fn user_code_segment() -> Descriptor {
-        Descriptor::UserSegment(DescriptorFlags::USER_CODE64.bits())
+        Self::code_segment(PrivilegeLevel::Ring3)
     }
 
     /// Creates a TSS system descriptor for the given TSS.
@@ -439,19 +671,45 @@ impl Descriptor {
     /// being used.
     #[inline]
     pub unsafe fn tss_segment_unchecked(tss: *const TaskStateSegment) -> Descriptor {
-        use self::DescriptorFlags as Flags;
         use core::mem::size_of;
 
+        // limit (the `-1` in needed since the bound is inclusive)
+        Self::tss_segment_with_limit(tss, (size_of::<TaskStateSegment>() - 1) as u32)
+    }
+
+    /// Creates a TSS system descriptor for the given TSS that also covers an I/O permission
+    /// bitmap of `iomap_size` bytes and the `0xff` byte following it.
+    ///
+    /// The segment limit is computed by [`TaskStateSegment::descriptor_limit`]. The CPU treats
+    /// all ports whose bits lie beyond the limit as inaccessible, so a descriptor created by
+    /// [`Descriptor::tss_segment`] effectively disables the I/O permission bitmap.
+    ///
+    /// # Safety
+    /// The caller must ensure that the TSS is followed by a valid I/O permission bitmap of
+    /// `iomap_size` bytes at offset [`iomap_base`](TaskStateSegment::iomap_base) and a final
+    /// `0xff` byte, and that this memory is valid for as long as the descriptor is being used.
+    #[inline]
+    pub unsafe fn tss_segment_with_iomap(
+        tss: &'static TaskStateSegment,
+        iomap_size: u16,
+    ) -> Descriptor {
+        Self::tss_segment_with_limit(tss, tss.descriptor_limit(iomap_size))
+    }
+
+    fn tss_segment_with_limit(tss: *const TaskStateSegment, limit: u32) -> Descriptor {
+        use self::DescriptorFlags as Flags;
+
         let ptr = tss as u64;
 
         let mut low = Flags::PRESENT.bits();
         // base
         low.set_bits(16..40, ptr.get_bits(0..24));
         low.set_bits(56..64, ptr.get_bits(24..32));
-        // limit (the `-1` in needed since the bound is inclusive)
-        low.set_bits(0..16, (size_of::<TaskStateSegment>() - 1) as u64);
+        // limit
+        low.set_bits(0..16, limit.get_bits(0..16).into());
+        low.set_bits(48..52, limit.get_bits(16..20).into());
         // type (0b1001 = available 64-bit tss)
-        low.set_bits(40..44, 0b1001);
+        low.set_bits(40..44, TSS_AVAILABLE);
 
         let mut high = 0;
         high.set_bits(0..32, ptr.get_bits(32..64));
@@ -460,6 +718,53 @@ impl Descriptor {
     }
 }
 
+/// The decoded fields of a raw 8-byte segment descriptor.
+///
+/// Returned by [`Descriptor::decode`]. For system descriptors (such as TSS
+/// descriptors) in 64-bit mode, only bits 0 to 31 of the base are contained in
+/// the first 8-byte [`Entry`]; the upper 32 bits are stored in the next entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DescriptorInfo {
+    /// The 32-bit base address stored in the descriptor.
+    pub base: u32,
+    /// The 20-bit limit field stored in the descriptor.
+    ///
+    /// If [`granularity`](Self::granularity) is set, the limit is given in
+    /// units of 4096 bytes, see [`byte_limit`](Self::byte_limit).
+    pub limit: u32,
+    /// The 4-bit type field of the descriptor.
+    ///
+    /// For user segments, this field contains the accessed, writable,
+    /// conforming and executable bits. For system segments, it contains the
+    /// system descriptor type (e.g. `0b1001` for an available 64-bit TSS).
+    pub segment_type: u8,
+    /// Whether this is a code or data segment (as opposed to a system segment).
+    pub user_segment: bool,
+    /// The Descriptor Privilege Level (DPL).
+    pub dpl: PrivilegeLevel,
+    /// Whether the segment is present.
+    pub present: bool,
+    /// Whether this is a 64-bit code segment.
+    pub long_mode: bool,
+    /// Whether the segment uses 32-bit (as opposed to 16-bit) operands.
+    pub default_size: bool,
+    /// Whether the limit is scaled by 4096 bytes.
+    pub granularity: bool,
+}
+
+impl DescriptorInfo {
+    /// Returns the limit in bytes, taking the [`granularity`](Self::granularity)
+    /// into account.
+    #[inline]
+    pub const fn byte_limit(&self) -> u32 {
+        if self.granularity {
+            (self.limit << 12) | 0xfff
+        } else {
+            self.limit
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::DescriptorFlags as Flags;
@@ -478,6 +783,30 @@ mod tests {
         assert_eq!(Flags::USER_DATA.bits(),     0x00cff3000000ffff);
     }
 
+    #[test]
+    #[rustfmt::skip]
+    pub fn segments_with_dpl() {
+        use PrivilegeLevel::*;
+
+        let raw = |descriptor| match descriptor {
+            Descriptor::UserSegment(raw) => raw,
+            Descriptor::SystemSegment(_, _) => unreachable!(),
+        };
+        assert_eq!(raw(Descriptor::code_segment(Ring0)), 0x00af9b000000ffff);
+        assert_eq!(raw(Descriptor::code_segment(Ring1)), 0x00afbb000000ffff);
+        assert_eq!(raw(Descriptor::code_segment(Ring2)), 0x00afdb000000ffff);
+        assert_eq!(raw(Descriptor::code_segment(Ring3)), 0x00affb000000ffff);
+        assert_eq!(raw(Descriptor::data_segment(Ring0)), 0x00cf93000000ffff);
+        assert_eq!(raw(Descriptor::data_segment(Ring1)), 0x00cfb3000000ffff);
+        assert_eq!(raw(Descriptor::data_segment(Ring2)), 0x00cfd3000000ffff);
+        assert_eq!(raw(Descriptor::data_segment(Ring3)), 0x00cff3000000ffff);
+
+        assert_eq!(raw(Descriptor::kernel_code_segment()), Flags::KERNEL_CODE64.bits());
+        assert_eq!(raw(Descriptor::kernel_data_segment()), Flags::KERNEL_DATA.bits());
+        assert_eq!(raw(Descriptor::user_code_segment()), Flags::USER_CODE64.bits());
+        assert_eq!(raw(Descriptor::user_data_segment()), Flags::USER_DATA.bits());
+    }
+
     // Makes a GDT that has two free slots
     fn make_six_entry_gdt() -> GlobalDescriptorTable {
         let mut gdt = GlobalDescriptorTable::new();
@@ -527,6 +856,64 @@ mod tests {
         gdt.append(Descriptor::tss_segment(&TSS));
     }
 
+    #[test]
+    pub fn append_segment_bundles() {
+        let mut gdt = GlobalDescriptorTable::new();
+        let kernel = gdt.append_kernel_segments();
+        let user = gdt.append_user_segments();
+
+        assert_eq!(kernel.code, SegmentSelector::new(1, PrivilegeLevel::Ring0));
+        assert_eq!(kernel.data, SegmentSelector::new(2, PrivilegeLevel::Ring0));
+        assert_eq!(user.data, SegmentSelector::new(3, PrivilegeLevel::Ring3));
+        assert_eq!(user.code, SegmentSelector::new(4, PrivilegeLevel::Ring3));
+        assert_eq!(gdt.entries()[1].raw(), Flags::KERNEL_CODE64.bits());
+        assert_eq!(gdt.entries()[4].raw(), Flags::USER_CODE64.bits());
+    }
+
+    #[test]
+    pub fn append_tss_dpl() {
+        let mut gdt = make_six_entry_gdt();
+        let selector = gdt.append_tss(&TSS, PrivilegeLevel::Ring3);
+        assert_eq!(selector.index(), 6);
+        assert_eq!(selector.rpl(), PrivilegeLevel::Ring3);
+        match Descriptor::tss_segment(&TSS) {
+            Descriptor::SystemSegment(low, high) => {
+                assert_eq!(gdt.entries()[6].raw(), low | Flags::DPL_RING_3.bits());
+                assert_eq!(gdt.entries()[7].raw(), high);
+            }
+            Descriptor::UserSegment(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    pub fn tss_busy_bit() {
+        let mut gdt = make_six_entry_gdt();
+        let selector = gdt.append_tss(&TSS, PrivilegeLevel::Ring0);
+        assert!(!gdt.is_tss_busy(selector));
+
+        let mut raw = [0; 8];
+        for (raw, entry) in raw.iter_mut().zip(gdt.entries()) {
+            *raw = entry.raw();
+        }
+        raw[6] |= 1 << 41;
+        let gdt = GlobalDescriptorTable::<8>::from_raw_entries(&raw);
+        assert!(gdt.is_tss_busy(selector));
+
+        #[cfg(all(feature = "instructions", target_arch = "x86_64"))]
+        {
+            gdt.clear_tss_busy(selector);
+            assert!(!gdt.is_tss_busy(selector));
+            assert_eq!(gdt.entries()[6].raw(), raw[6] & !(1 << 41));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn tss_busy_non_tss_selector() {
+        let gdt = make_full_gdt();
+        gdt.is_tss_busy(SegmentSelector::new(1, PrivilegeLevel::Ring0));
+    }
+
     #[test]
     pub fn from_entries() {
         let raw = [0, Flags::KERNEL_CODE64.bits(), Flags::KERNEL_DATA.bits()];
@@ -535,6 +922,80 @@ mod tests {
         assert_eq!(gdt.entries().len(), 3);
     }
 
+    #[test]
+    pub fn decode_descriptor() {
+        let info = Descriptor::decode(Flags::KERNEL_CODE64.bits());
+        assert_eq!(
+            info,
+            DescriptorInfo {
+                base: 0,
+                limit: 0xf_ffff,
+                segment_type: 0b1011,
+                user_segment: true,
+                dpl: PrivilegeLevel::Ring0,
+                present: true,
+                long_mode: true,
+                default_size: false,
+                granularity: true,
+            }
+        );
+        assert_eq!(info.byte_limit(), 0xffff_ffff);
+
+        let info = Descriptor::decode(Flags::USER_DATA.bits());
+        assert_eq!(info.segment_type, 0b0011);
+        assert_eq!(info.dpl, PrivilegeLevel::Ring3);
+        assert!(!info.long_mode);
+        assert!(info.default_size);
+
+        // 16-bit data segment with base 0x12345678 and limit 0xabcde
+        let info = Descriptor::decode(0x120a_9234_5678_bcde);
+        assert_eq!(info.base, 0x1234_5678);
+        assert_eq!(info.limit, 0xa_bcde);
+        assert_eq!(info.byte_limit(), 0xa_bcde);
+        assert!(!info.default_size && !info.granularity);
+
+        let tss_low = match Descriptor::tss_segment(&TSS) {
+            Descriptor::SystemSegment(low, _) => low,
+            Descriptor::UserSegment(_) => unreachable!(),
+        };
+        let info = Descriptor::decode(tss_low);
+        assert_eq!(info.segment_type, 0b1001);
+        assert!(!info.user_segment);
+        assert!(info.present);
+        assert_eq!(
+            info.limit,
+            (core::mem::size_of::<TaskStateSegment>() - 1) as u32
+        );
+        assert_eq!(info.base, &TSS as *const _ as u64 as u32);
+    }
+
+    #[test]
+    pub fn tss_segment_with_iomap() {
+        #[repr(C)]
+        struct TssWithIomap {
+            tss: TaskStateSegment,
+            iomap: [u8; 8],
+            end: u8,
+        }
+
+        static TSS_WITH_IOMAP: TssWithIomap = TssWithIomap {
+            tss: TaskStateSegment::new(),
+            iomap: [0; 8],
+            end: 0xff,
+        };
+
+        let tss = &TSS_WITH_IOMAP.tss;
+        let tss_low = match unsafe { Descriptor::tss_segment_with_iomap(tss, 8) } {
+            Descriptor::SystemSegment(low, _) => low,
+            Descriptor::UserSegment(_) => unreachable!(),
+        };
+        let info = Descriptor::decode(tss_low);
+        // The limit includes the final `0xff` byte, but not the padding of the struct.
+        let end = &TSS_WITH_IOMAP.end as *const u8 as usize;
+        assert_eq!(info.limit as usize, end - tss as *const _ as usize);
+        assert_eq!(info.segment_type, 0b1001);
+    }
+
     #[test]
     pub fn descriptor_dpl() {
         assert_eq!(
@@ -759,6 +759,21 @@ pub type PageFaultHandlerFunc =
 #[derive(Copy, Clone, Debug)]
 pub struct PageFaultHandlerFunc(());
 
+/// Returns the virtual address that caused the current page fault, as stored in CR2.
+///
+/// This is a shorthand for reading [`Cr2`](crate::registers::control::Cr2) at the start of a
+/// [`PageFaultHandlerFunc`]. It must be called before anything else could cause a nested page
+/// fault, since the CPU overwrites CR2 on every page fault.
+///
+/// Page faults are never raised for non-canonical addresses (those cause a general protection
+/// or stack fault instead), so the value is converted using [`VirtAddr::new_truncate`]. Use
+/// [`Cr2::read`](crate::registers::control::Cr2::read) to check the value instead.
+#[cfg(all(feature = "instructions", target_arch = "x86_64"))]
+#[inline]
+pub fn page_fault_address() -> VirtAddr {
+    VirtAddr::new_truncate(crate::registers::control::Cr2::read_raw())
+}
+
 /// A handler function that must not return, e.g. for a machine check exception.
 ///
 /// This type alias is only usable with the `abi_x86_interrupt` feature enabled.
@@ -809,6 +824,43 @@ impl<F> Entry<F> {
         }
     }
 
+    /// Creates a present IDT entry for the given handler address, code selector, and options.
+    ///
+    /// Unlike [`set_handler_addr`](Self::set_handler_addr), this doesn't read the currently
+    /// active code segment, so it can be used to build a complete IDT at compile time. The code
+    /// selector of `options` is replaced by `cs` and the present bit is set. See
+    /// [`EntryOptions::minimal`] for building the options in a const context.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `addr` is the address of a valid interrupt handler function,
+    /// and the signature of such a function is correct for the entry type. The address must
+    /// stay valid for as long as the entry is part of a loaded IDT. `cs` must point to a valid,
+    /// long-mode code segment whenever the interrupt can occur.
+    #[cfg_attr(
+        not(all(feature = "instructions", target_arch = "x86_64")),
+        allow(rustdoc::broken_intra_doc_links)
+    )]
+    #[inline]
+    pub const unsafe fn new_const(
+        addr: VirtAddr,
+        cs: SegmentSelector,
+        options: EntryOptions,
+    ) -> Self {
+        let addr = addr.as_u64();
+        Entry {
+            pointer_low: addr as u16,
+            pointer_middle: (addr >> 16) as u16,
+            pointer_high: (addr >> 32) as u32,
+            options: EntryOptions {
+                cs,
+                bits: options.bits | 1 << 15,
+            },
+            reserved: 0,
+            phantom: PhantomData,
+        }
+    }
+
     /// Sets the handler address for the IDT entry and sets the following defaults:
     ///   - The code selector is the code segment currently active in the CPU
     ///   - The present bit is set
@@ -932,14 +984,59 @@ impl fmt::Debug for EntryOptions {
 impl EntryOptions {
     /// Creates a minimal options field with all the must-be-one bits set. This
     /// means the CS selector, IST, and DPL field are all 0.
+    ///
+    /// The resulting options describe a non-present 64-bit interrupt gate that disables
+    /// interrupts on handler invocation. Together with the `with_*` methods, this allows
+    /// building the options in a const context, e.g. for [`Entry::new_const`].
     #[inline]
-    const fn minimal() -> Self {
+    pub const fn minimal() -> Self {
         EntryOptions {
             cs: SegmentSelector(0),
             bits: 0b1110_0000_0000, // Default to a 64-bit Interrupt Gate
         }
     }
 
+    /// Returns the options with interrupts disabled or enabled on handler invocation.
+    ///
+    /// This is the const equivalent of [`disable_interrupts`](Self::disable_interrupts).
+    #[inline]
+    pub const fn with_interrupts_disabled(mut self, disable: bool) -> Self {
+        if disable {
+            self.bits &= !(1 << 8);
+        } else {
+            self.bits |= 1 << 8;
+        }
+        self
+    }
+
+    /// Returns the options with the given required privilege level (DPL).
+    ///
+    /// This is the const equivalent of [`set_privilege_level`](Self::set_privilege_level).
+    #[inline]
+    pub const fn with_privilege_level(mut self, dpl: PrivilegeLevel) -> Self {
+        self.bits = (self.bits & !(0b11 << 13)) | (dpl as u16) << 13;
+        self
+    }
+
+    /// Returns the options with the given Interrupt Stack Table (IST) index.
+    ///
+    /// This is the const equivalent of [`set_stack_index`](Self::set_stack_index).
+    ///
+    /// This function panics if the index is not in the range 0..7.
+    ///
+    /// ## Safety
+    ///
+    /// This function is unsafe because the caller must ensure that the passed stack index is
+    /// valid and not used by other interrupts. Otherwise, memory safety violations are possible.
+    #[inline]
+    pub const unsafe fn with_stack_index(mut self, index: u16) -> Self {
+        assert!(index < 7, "IST index must be in the range 0..7");
+        // The hardware IST index starts at 1, but our software IST index
+        // starts at 0. Therefore we need to add 1 here.
+        self.bits = (self.bits & !0b111) | (index + 1);
+        self
+    }
+
     /// Set the code segment that will be used by this interrupt.
     ///
     /// ## Safety
@@ -1054,6 +1151,24 @@ impl InterruptStackFrame {
     pub unsafe fn as_mut(&mut self) -> Volatile<&mut InterruptStackFrameValue> {
         Volatile::new(&mut self.0)
     }
+
+    /// Gives plain mutable access to the contents of the interrupt stack frame.
+    ///
+    /// This is intended for handlers that don't return normally, but rebuild the frame and
+    /// return through a manual `iretq` themselves. In all other cases, [`as_mut`](Self::as_mut)
+    /// should be used instead.
+    ///
+    /// ## Safety
+    ///
+    /// In addition to the requirements of [`as_mut`](Self::as_mut), the caller must be aware
+    /// that LLVM optimizations remove non-volatile modifications of the interrupt stack frame
+    /// if the handler returns normally. So writes through the returned reference are only
+    /// guaranteed to take effect if the frame is subsequently read in a way the compiler can't
+    /// elide, e.g. by an `asm!` block that performs the `iretq`.
+    #[inline]
+    pub unsafe fn value_mut(&mut self) -> &mut InterruptStackFrameValue {
+        &mut self.0
+    }
 }
 
 impl Deref for InterruptStackFrame {
@@ -1072,6 +1187,13 @@ impl fmt::Debug for InterruptStackFrame {
     }
 }
 
+impl fmt::Display for InterruptStackFrame {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
 /// Represents the interrupt stack frame pushed by the CPU on interrupt or exception entry.
 #[derive(Clone, Copy)]
 #[repr(C)]
@@ -1160,6 +1282,22 @@ impl fmt::Debug for InterruptStackFrameValue {
     }
 }
 
+/// Formats the stack frame on a single line, e.g.
+/// `rip=0x1000 cs=0x8 rflags=0x202 rsp=0x2000 ss=0x0`.
+impl fmt::Display for InterruptStackFrameValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "rip={:#x} cs={:#x} rflags={:#x} rsp={:#x} ss={:#x}",
+            self.instruction_pointer.as_u64(),
+            self.code_segment.0,
+            self.cpu_flags.bits(),
+            self.stack_pointer.as_u64(),
+            self.stack_segment.0,
+        )
+    }
+}
+
 bitflags! {
     /// Describes an page fault error code.
     ///
@@ -1208,6 +1346,69 @@ bitflags! {
     }
 }
 
+impl PageFaultErrorCode {
+    /// Returns the kind of memory access that caused the page fault.
+    ///
+    /// An instruction fetch is reported as [`AccessKind::InstructionFetch`]
+    /// regardless of the [`CAUSED_BY_WRITE`](Self::CAUSED_BY_WRITE) flag.
+    #[inline]
+    pub const fn access_kind(&self) -> AccessKind {
+        if self.contains(Self::INSTRUCTION_FETCH) {
+            AccessKind::InstructionFetch
+        } else if self.contains(Self::CAUSED_BY_WRITE) {
+            AccessKind::Write
+        } else {
+            AccessKind::Read
+        }
+    }
+
+    /// Returns `true` if the page fault was caused by a page-protection
+    /// violation and `false` if it was caused by a not-present page.
+    #[inline]
+    pub const fn caused_by_protection(&self) -> bool {
+        self.contains(Self::PROTECTION_VIOLATION)
+    }
+}
+
+/// Formats the set flags as a compact list, e.g.
+/// `[PROTECTION_VIOLATION, CAUSED_BY_WRITE, USER_MODE]`.
+///
+/// Unknown bits are appended as a single hexadecimal value.
+impl fmt::Display for PageFaultErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("[")?;
+        let mut first = true;
+        let mut entry = |f: &mut fmt::Formatter, args: fmt::Arguments| {
+            if !first {
+                f.write_str(", ")?;
+            }
+            first = false;
+            f.write_fmt(args)
+        };
+        for (name, _) in self.iter_names() {
+            entry(f, format_args!("{}", name))?;
+        }
+        let unknown = self.bits() & !Self::all().bits();
+        if unknown != 0 {
+            entry(f, format_args!("{:#x}", unknown))?;
+        }
+        f.write_str("]")
+    }
+}
+
+/// The kind of memory access that caused a page fault.
+///
+/// Returned by [`PageFaultErrorCode::access_kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccessKind {
+    /// The access was a data read.
+    Read,
+    /// The access was a data write.
+    Write,
+    /// The access was an instruction fetch.
+    InstructionFetch,
+}
+
 /// Describes an error code referencing a segment selector.
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(transparent)]
@@ -1270,6 +1471,25 @@ impl fmt::Debug for SelectorErrorCode {
     }
 }
 
+/// Formats the error code like `GDT index 5 (external)`.
+impl fmt::Display for SelectorErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_null() {
+            return f.write_str("null selector");
+        }
+        let table = match self.descriptor_table() {
+            DescriptorTable::Gdt => "GDT",
+            DescriptorTable::Idt => "IDT",
+            DescriptorTable::Ldt => "LDT",
+        };
+        write!(f, "{} index {}", table, self.index())?;
+        if self.external() {
+            f.write_str(" (external)")?;
+        }
+        Ok(())
+    }
+}
+
 /// The possible descriptor table values.
 ///
 /// Used by the [`SelectorErrorCode`] to indicate which table caused the error.
@@ -1725,6 +1945,40 @@ mod test {
         dbg!(InterruptDescriptorTable::new());
     }
 
+    #[test]
+    fn entry_new_const() {
+        const OPTIONS: EntryOptions = unsafe {
+            EntryOptions::minimal()
+                .with_privilege_level(PrivilegeLevel::Ring3)
+                .with_interrupts_disabled(false)
+                .with_stack_index(2)
+        };
+        const ENTRY: Entry<HandlerFunc> = unsafe {
+            Entry::new_const(
+                VirtAddr::new_truncate(0xffff_8000_1234_5678),
+                SegmentSelector(0x8),
+                OPTIONS,
+            )
+        };
+
+        assert_eq!(ENTRY.handler_addr(), VirtAddr::new(0xffff_8000_1234_5678));
+        assert_eq!(ENTRY.options.cs, SegmentSelector(0x8));
+        assert!(ENTRY.options.present());
+        assert_eq!(ENTRY.options.privilege_level(), PrivilegeLevel::Ring3);
+        assert_eq!(ENTRY.options.stack_index(), Some(2));
+
+        let mut options = EntryOptions::minimal();
+        unsafe {
+            options
+                .set_code_selector(SegmentSelector(0x8))
+                .set_present(true)
+                .set_privilege_level(PrivilegeLevel::Ring3)
+                .disable_interrupts(false)
+                .set_stack_index(2);
+        }
+        assert!(ENTRY.options == options);
+    }
+
     #[test]
     fn entry_derive_test() {
         fn foo(_: impl Copy + PartialEq + fmt::Debug) {}
@@ -1739,6 +1993,104 @@ mod test {
         })
     }
 
+    #[test]
+    fn page_fault_error_code_classification() {
+        use PageFaultErrorCode as Code;
+
+        for (code, access_kind) in [
+            (Code::empty(), AccessKind::Read),
+            (Code::PROTECTION_VIOLATION, AccessKind::Read),
+            (Code::USER_MODE, AccessKind::Read),
+            (Code::CAUSED_BY_WRITE, AccessKind::Write),
+            (Code::CAUSED_BY_WRITE | Code::USER_MODE, AccessKind::Write),
+            (
+                Code::CAUSED_BY_WRITE | Code::PROTECTION_VIOLATION,
+                AccessKind::Write,
+            ),
+            (Code::INSTRUCTION_FETCH, AccessKind::InstructionFetch),
+            (
+                Code::INSTRUCTION_FETCH | Code::PROTECTION_VIOLATION,
+                AccessKind::InstructionFetch,
+            ),
+            (
+                Code::INSTRUCTION_FETCH | Code::CAUSED_BY_WRITE,
+                AccessKind::InstructionFetch,
+            ),
+        ] {
+            assert_eq!(code.access_kind(), access_kind, "{:?}", code);
+        }
+
+        assert!(!Code::empty().caused_by_protection());
+        assert!(!Code::USER_MODE.caused_by_protection());
+        assert!(Code::PROTECTION_VIOLATION.caused_by_protection());
+        assert!((Code::PROTECTION_VIOLATION | Code::CAUSED_BY_WRITE).caused_by_protection());
+    }
+
+    #[test]
+    fn page_fault_error_code_display() {
+        assert_eq!(format!("{}", PageFaultErrorCode::empty()), "[]");
+        assert_eq!(
+            format!(
+                "{}",
+                PageFaultErrorCode::PROTECTION_VIOLATION
+                    | PageFaultErrorCode::CAUSED_BY_WRITE
+                    | PageFaultErrorCode::USER_MODE
+            ),
+            "[PROTECTION_VIOLATION, CAUSED_BY_WRITE, USER_MODE]"
+        );
+        assert_eq!(
+            format!("{}", PageFaultErrorCode::INSTRUCTION_FETCH),
+            "[INSTRUCTION_FETCH]"
+        );
+        assert_eq!(
+            format!("{}", PageFaultErrorCode::from_bits_retain(1 | 1 << 8)),
+            "[PROTECTION_VIOLATION, 0x100]"
+        );
+        assert_eq!(
+            format!("{:#}", PageFaultErrorCode::SGX | PageFaultErrorCode::RMP),
+            "[SGX, RMP]"
+        );
+    }
+
+    #[test]
+    fn selector_error_code_display() {
+        assert_eq!(
+            format!("{}", SelectorErrorCode::new_truncate(0)),
+            "null selector"
+        );
+        assert_eq!(
+            format!("{}", SelectorErrorCode::new_truncate(5 << 3 | 1)),
+            "GDT index 5 (external)"
+        );
+        assert_eq!(
+            format!("{}", SelectorErrorCode::new_truncate(0x20 << 3 | 0b010)),
+            "IDT index 32"
+        );
+        assert_eq!(
+            format!("{}", SelectorErrorCode::new_truncate(2 << 3 | 0b100)),
+            "LDT index 2"
+        );
+    }
+
+    #[test]
+    fn isr_frame_display() {
+        let frame = InterruptStackFrameValue::new(
+            VirtAddr::new(0x1000),
+            SegmentSelector(0x8),
+            RFlags::INTERRUPT_FLAG,
+            VirtAddr::new(0xffff_8000_0000_2000),
+            SegmentSelector(0),
+        );
+        assert_eq!(
+            format!("{}", frame),
+            "rip=0x1000 cs=0x8 rflags=0x200 rsp=0xffff800000002000 ss=0x0"
+        );
+        assert_eq!(
+            format!("{}", InterruptStackFrame(frame)),
+            format!("{}", frame)
+        );
+    }
+
     #[test]
     fn isr_frame_manipulation() {
         let mut frame = InterruptStackFrame(InterruptStackFrameValue {
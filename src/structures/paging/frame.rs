@@ -4,9 +4,14 @@ use super::page::AddressNotAligned;
 use crate::structures::paging::page::{PageSize, Size4KiB};
 use crate::PhysAddr;
 use core::fmt;
+#[cfg(feature = "step_trait")]
+use core::iter::Step;
 use core::marker::PhantomData;
 use core::ops::{Add, AddAssign, Sub, SubAssign};
 
+/// The highest valid physical address (52 bits).
+const MAX_PHYS_ADDR: u64 = 0x000f_ffff_ffff_ffff;
+
 /// A physical memory frame.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(C)]
@@ -31,14 +36,18 @@ impl<S: PageSize> PhysFrame<S> {
         Ok(unsafe { PhysFrame::from_start_address_unchecked(address) })
     }
 
-    /// Returns the frame that starts at the given virtual address.
+    /// Returns the frame that starts at the given physical address.
+    ///
+    /// Unlike [`from_start_address`](Self::from_start_address), this skips the alignment check,
+    /// which makes it useful in hot paths where the alignment is already known.
     ///
     /// ## Safety
     ///
-    /// The address must be correctly aligned.
+    /// The address must be aligned to `S::SIZE`. This is only checked in debug builds.
     #[inline]
     #[rustversion::attr(since(1.61), const)]
     pub unsafe fn from_start_address_unchecked(start_address: PhysAddr) -> Self {
+        debug_assert!(start_address.is_aligned_u64(S::SIZE));
         PhysFrame {
             start_address,
             size: PhantomData,
@@ -69,6 +78,52 @@ impl<S: PageSize> PhysFrame<S> {
         S::SIZE
     }
 
+    /// Returns the frame of size `T` that contains the start address of this frame.
+    ///
+    /// If `T` is larger than `S`, this is the frame of size `T` that contains this frame. If `T`
+    /// is smaller than `S`, it is the first frame of size `T` inside this frame.
+    ///
+    /// This is useful for finding huge frame boundaries, e.g.
+    /// `frame.align_down_to::<Size2MiB>()` returns the 2MiB frame containing a 4KiB frame.
+    #[inline]
+    pub fn align_down_to<T: PageSize>(self) -> PhysFrame<T> {
+        PhysFrame::containing_address(self.start_address)
+    }
+
+    /// Returns the first frame of size `T` that starts at or after the start of this frame.
+    ///
+    /// See [`PhysAddr::align_up`] for details on the alignment.
+    ///
+    /// ## Panics
+    ///
+    /// This function panics if the resulting address has a bit in the range 52 to 64 set.
+    #[inline]
+    pub fn align_up_to<T: PageSize>(self) -> PhysFrame<T> {
+        PhysFrame::containing_address(self.start_address.align_up(T::SIZE))
+    }
+
+    /// Returns the frame `count` frames after this frame.
+    ///
+    /// Returns `None` if the start address of the resulting frame would not be
+    /// a valid physical address.
+    #[inline]
+    pub fn checked_add(self, count: u64) -> Option<Self> {
+        let offset = count.checked_mul(S::SIZE)?;
+        let addr = self.start_address.as_u64().checked_add(offset)?;
+        Some(PhysFrame::containing_address(PhysAddr::try_new(addr).ok()?))
+    }
+
+    /// Returns the frame `count` frames before this frame.
+    ///
+    /// Returns `None` if the start address of the resulting frame would be
+    /// negative.
+    #[inline]
+    pub fn checked_sub(self, count: u64) -> Option<Self> {
+        let offset = count.checked_mul(S::SIZE)?;
+        let addr = self.start_address.as_u64().checked_sub(offset)?;
+        Some(PhysFrame::containing_address(PhysAddr::new(addr)))
+    }
+
     /// Returns a range of frames, exclusive `end`.
     #[inline]
     #[rustversion::attr(since(1.61), const)]
@@ -132,6 +187,48 @@ impl<S: PageSize> Sub<PhysFrame<S>> for PhysFrame<S> {
     }
 }
 
+#[cfg(feature = "step_trait")]
+impl<S: PageSize> Step for PhysFrame<S> {
+    fn steps_between(start: &Self, end: &Self) -> (usize, Option<usize>) {
+        use core::convert::TryFrom;
+
+        if let Some(steps) = end
+            .start_address
+            .as_u64()
+            .checked_sub(start.start_address.as_u64())
+        {
+            let steps = steps / S::SIZE;
+            let steps = usize::try_from(steps).ok();
+            (steps.unwrap_or(usize::MAX), steps)
+        } else {
+            (0, None)
+        }
+    }
+
+    fn forward_checked(start: Self, count: usize) -> Option<Self> {
+        use core::convert::TryFrom;
+
+        let count = u64::try_from(count).ok()?.checked_mul(S::SIZE)?;
+        let addr = start.start_address.as_u64().checked_add(count)?;
+        let start_address = PhysAddr::try_new(addr).ok()?;
+        Some(Self {
+            start_address,
+            size: PhantomData,
+        })
+    }
+
+    fn backward_checked(start: Self, count: usize) -> Option<Self> {
+        use core::convert::TryFrom;
+
+        let count = u64::try_from(count).ok()?.checked_mul(S::SIZE)?;
+        let addr = start.start_address.as_u64().checked_sub(count)?;
+        Some(Self {
+            start_address: PhysAddr::new(addr),
+            size: PhantomData,
+        })
+    }
+}
+
 /// An range of physical memory frames, exclusive the upper bound.
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(C)]
@@ -164,6 +261,30 @@ impl<S: PageSize> PhysFrameRange<S> {
     pub fn size(&self) -> u64 {
         S::SIZE * self.len()
     }
+
+    /// Splits the range into the frames before `frame` and the frames starting at `frame`.
+    ///
+    /// If `frame` lies outside of the range, one of the two halves is empty.
+    #[inline]
+    pub fn split_at(&self, frame: PhysFrame<S>) -> (PhysFrameRange<S>, PhysFrameRange<S>) {
+        let mid = frame.min(self.end).max(self.start);
+        (
+            PhysFrameRange {
+                start: self.start,
+                end: mid,
+            },
+            PhysFrameRange {
+                start: mid,
+                end: self.end,
+            },
+        )
+    }
+
+    /// Returns whether the given frame is contained in the range.
+    #[inline]
+    pub fn contains(&self, frame: PhysFrame<S>) -> bool {
+        self.start <= frame && frame < self.end
+    }
 }
 
 impl<S: PageSize> Iterator for PhysFrameRange<S> {
@@ -222,6 +343,12 @@ impl<S: PageSize> PhysFrameRangeInclusive<S> {
     pub fn size(&self) -> u64 {
         S::SIZE * self.len()
     }
+
+    /// Returns whether the given frame is contained in the range.
+    #[inline]
+    pub fn contains(&self, frame: PhysFrame<S>) -> bool {
+        self.start <= frame && frame <= self.end
+    }
 }
 
 impl<S: PageSize> Iterator for PhysFrameRangeInclusive<S> {
@@ -231,7 +358,17 @@ impl<S: PageSize> Iterator for PhysFrameRangeInclusive<S> {
     fn next(&mut self) -> Option<Self::Item> {
         if self.start <= self.end {
             let frame = self.start;
-            self.start += 1;
+
+            // If the end of the inclusive range is the maximum frame possible for size S,
+            // incrementing start until it is greater than the end would create an invalid
+            // physical address. So instead, in that case we decrement end rather than
+            // incrementing start.
+            let max_frame_addr = PhysAddr::new(MAX_PHYS_ADDR).align_down_u64(S::SIZE);
+            if self.start.start_address() < max_frame_addr {
+                self.start += 1;
+            } else {
+                self.end -= 1;
+            }
             Some(frame)
         } else {
             None
@@ -251,6 +388,27 @@ impl<S: PageSize> fmt::Debug for PhysFrameRangeInclusive<S> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::structures::paging::{Size1GiB, Size2MiB};
+
+    #[test]
+    pub fn test_frame_align_to() {
+        let frame: PhysFrame = PhysFrame::containing_address(PhysAddr::new(0x20_0000));
+        let huge = frame.align_up_to::<Size2MiB>();
+        assert_eq!(huge.start_address(), PhysAddr::new(0x20_0000));
+        assert_eq!(frame.align_down_to::<Size2MiB>(), huge);
+
+        let frame = frame + 1;
+        assert_eq!(frame.align_down_to::<Size2MiB>(), huge);
+        assert_eq!(frame.align_up_to::<Size2MiB>(), huge + 1);
+        assert_eq!(
+            frame.align_up_to::<Size1GiB>().start_address(),
+            PhysAddr::new(0x4000_0000)
+        );
+        assert_eq!(
+            huge.align_down_to::<Size4KiB>().start_address(),
+            huge.start_address()
+        );
+    }
     #[test]
     pub fn test_frame_range_len() {
         let start_addr = PhysAddr::new(0xdead_beaf);
@@ -263,4 +421,123 @@ mod tests {
         let range_inclusive = PhysFrameRangeInclusive { start, end };
         assert_eq!(range_inclusive.len(), 51);
     }
+
+    #[test]
+    pub fn test_frame_range_split_at() {
+        let start = PhysFrame::<Size4KiB>::containing_address(PhysAddr::new(0x10_0000));
+        let end = start + 10;
+        let range = PhysFrameRange { start, end };
+
+        let (low, high) = range.split_at(start + 4);
+        assert_eq!(
+            low,
+            PhysFrameRange {
+                start,
+                end: start + 4
+            }
+        );
+        assert_eq!(
+            high,
+            PhysFrameRange {
+                start: start + 4,
+                end
+            }
+        );
+
+        let (low, high) = range.split_at(start);
+        assert!(low.is_empty());
+        assert_eq!(high, range);
+
+        let (low, high) = range.split_at(start - 1);
+        assert!(low.is_empty());
+        assert_eq!(high, range);
+
+        let (low, high) = range.split_at(end);
+        assert_eq!(low, range);
+        assert!(high.is_empty());
+
+        let (low, high) = range.split_at(end + 1);
+        assert_eq!(low, range);
+        assert!(high.is_empty());
+    }
+
+    #[test]
+    pub fn test_frame_range_contains() {
+        let start = PhysFrame::<Size4KiB>::containing_address(PhysAddr::new(0x1000));
+        let end = start + 3;
+
+        let range = PhysFrame::range(start, end);
+        assert!(range.contains(start));
+        assert!(range.contains(start + 2));
+        assert!(!range.contains(end));
+        assert!(!range.contains(start - 1));
+
+        let range_inclusive = PhysFrame::range_inclusive(start, end);
+        assert!(range_inclusive.contains(start));
+        assert!(range_inclusive.contains(end));
+        assert!(!range_inclusive.contains(end + 1));
+
+        let empty = PhysFrame::range_inclusive(end, start);
+        assert!(empty.is_empty());
+        assert_eq!(empty.len(), 0);
+        assert!(!empty.contains(start));
+    }
+
+    #[test]
+    pub fn test_frame_debug() {
+        let frame = PhysFrame::<Size4KiB>::containing_address(PhysAddr::new(0x1000));
+        assert_eq!(format!("{:?}", frame), "PhysFrame[4KiB](0x1000)");
+        let frame = PhysFrame::<Size2MiB>::containing_address(PhysAddr::new(0x20_0000));
+        assert_eq!(format!("{:?}", frame), "PhysFrame[2MiB](0x200000)");
+        let frame = PhysFrame::<Size1GiB>::containing_address(PhysAddr::new(0x4000_0000));
+        assert_eq!(format!("{:?}", frame), "PhysFrame[1GiB](0x40000000)");
+    }
+
+    #[test]
+    pub fn test_frame_checked_arithmetic() {
+        let last = PhysFrame::<Size4KiB>::containing_address(PhysAddr::new(MAX_PHYS_ADDR));
+        assert_eq!(last.checked_add(1), None);
+        assert_eq!(last.checked_add(u64::MAX), None);
+        assert_eq!((last - 1).checked_add(1), Some(last));
+        assert_eq!(last.checked_sub(1), Some(last - 1));
+
+        let first = PhysFrame::<Size4KiB>::containing_address(PhysAddr::new(0));
+        assert_eq!(first.checked_sub(1), None);
+        assert_eq!(first.checked_add(2), Some(first + 2));
+    }
+
+    #[test]
+    pub fn test_frame_range_inclusive_overflow() {
+        let number = 10;
+
+        let end = PhysFrame::<Size4KiB>::containing_address(PhysAddr::new(MAX_PHYS_ADDR));
+        let start = end - number;
+
+        let mut range_inclusive = PhysFrame::range_inclusive(start, end);
+        assert_eq!(range_inclusive.len(), number + 1);
+        for i in 0..=number {
+            assert_eq!(range_inclusive.next(), Some(start + i));
+        }
+        assert_eq!(range_inclusive.next(), None);
+        assert!(range_inclusive.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "step_trait")]
+    fn frame_step() {
+        let first = PhysFrame::<Size4KiB>::containing_address(PhysAddr::new(0));
+        let last = PhysFrame::<Size4KiB>::containing_address(PhysAddr::new(MAX_PHYS_ADDR));
+
+        assert_eq!(Step::forward_checked(first, 2), Some(first + 2));
+        assert_eq!(Step::forward_checked(last, 1), None);
+        assert_eq!(Step::backward_checked(last, 1), Some(last - 1));
+        assert_eq!(Step::backward_checked(first, 1), None);
+        assert_eq!(Step::steps_between(&first, &(first + 5)), (5, Some(5)));
+        assert_eq!(Step::steps_between(&(first + 5), &first), (0, None));
+
+        // inclusive ranges up to the highest frame don't overflow
+        let frames: Vec<_> = (last - 2..=last).collect();
+        assert_eq!(frames, [last - 2, last - 1, last]);
+        assert_eq!((last - 2..=last).next_back(), Some(last));
+    }
 }
@@ -1,9 +1,12 @@
 //! Traits for abstracting away frame allocation and deallocation.
 
-use crate::structures::paging::{PageSize, PhysFrame};
+use crate::structures::paging::{frame::PhysFrameRange, PageSize, PhysFrame};
 
 /// A trait for types that can allocate a frame of memory.
 ///
+/// The trait is object safe, so an allocator can also be passed as
+/// `&mut dyn FrameAllocator<Size4KiB>` to the [`Mapper`](super::Mapper) methods.
+///
 /// # Safety
 ///
 /// The implementer of this trait must guarantee that the `allocate_frame`
@@ -22,4 +25,192 @@ pub trait FrameDeallocator<S: PageSize> {
     ///
     /// The caller must ensure that the passed frame is unused.
     unsafe fn deallocate_frame(&mut self, frame: PhysFrame<S>);
+
+    /// Deallocate all frames in the given range.
+    ///
+    /// The default implementation calls [`deallocate_frame`](Self::deallocate_frame) for each
+    /// frame in the range. Implementations can override this if they can free contiguous frames
+    /// more efficiently.
+    ///
+    /// ## Safety
+    ///
+    /// The caller must ensure that all frames in the range are unused.
+    unsafe fn deallocate_range(&mut self, range: PhysFrameRange<S>) {
+        for frame in range {
+            unsafe { self.deallocate_frame(frame) };
+        }
+    }
+}
+
+/// A frame allocator wrapper that counts allocated and deallocated frames.
+///
+/// Delegates all calls to the inner allocator. Only successful allocations
+/// are counted, i.e. calls to `allocate_frame` that return `None` don't
+/// change the counters. This is useful for finding frames that are allocated
+/// but never returned.
+#[derive(Debug, Clone)]
+pub struct CountingFrameAllocator<A> {
+    inner: A,
+    allocated: u64,
+    freed: u64,
+}
+
+impl<A> CountingFrameAllocator<A> {
+    /// Creates a new counting allocator that delegates to the given allocator.
+    #[inline]
+    pub const fn new(inner: A) -> Self {
+        Self {
+            inner,
+            allocated: 0,
+            freed: 0,
+        }
+    }
+
+    /// Returns the number of frames that were allocated.
+    #[inline]
+    pub const fn allocated(&self) -> u64 {
+        self.allocated
+    }
+
+    /// Returns the number of frames that were deallocated.
+    #[inline]
+    pub const fn freed(&self) -> u64 {
+        self.freed
+    }
+
+    /// Returns the number of frames that were allocated but not yet deallocated.
+    #[inline]
+    pub const fn in_use(&self) -> u64 {
+        self.allocated.saturating_sub(self.freed)
+    }
+
+    /// Returns a reference to the inner allocator.
+    #[inline]
+    pub const fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner allocator.
+    ///
+    /// Allocations and deallocations made through this reference are not counted.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut A {
+        &mut self.inner
+    }
+
+    /// Consumes the counting allocator and returns the inner allocator.
+    #[inline]
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+}
+
+unsafe impl<A, S> FrameAllocator<S> for CountingFrameAllocator<A>
+where
+    A: FrameAllocator<S>,
+    S: PageSize,
+{
+    #[inline]
+    fn allocate_frame(&mut self) -> Option<PhysFrame<S>> {
+        let frame = self.inner.allocate_frame()?;
+        self.allocated += 1;
+        Some(frame)
+    }
+}
+
+impl<A, S> FrameDeallocator<S> for CountingFrameAllocator<A>
+where
+    A: FrameDeallocator<S>,
+    S: PageSize,
+{
+    #[inline]
+    unsafe fn deallocate_frame(&mut self, frame: PhysFrame<S>) {
+        unsafe { self.inner.deallocate_frame(frame) };
+        self.freed += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structures::paging::Size4KiB;
+    use crate::PhysAddr;
+
+    struct BumpAllocator {
+        next: u64,
+        end: u64,
+    }
+
+    unsafe impl FrameAllocator<Size4KiB> for BumpAllocator {
+        fn allocate_frame(&mut self) -> Option<PhysFrame<Size4KiB>> {
+            if self.next >= self.end {
+                return None;
+            }
+            let frame = PhysFrame::containing_address(PhysAddr::new(self.next));
+            self.next += Size4KiB::SIZE;
+            Some(frame)
+        }
+    }
+
+    impl FrameDeallocator<Size4KiB> for BumpAllocator {
+        unsafe fn deallocate_frame(&mut self, _frame: PhysFrame<Size4KiB>) {}
+    }
+
+    #[test]
+    fn counting_frame_allocator() {
+        let mut allocator = CountingFrameAllocator::new(BumpAllocator {
+            next: 0x1000,
+            end: 0x4000,
+        });
+
+        let frame = allocator.allocate_frame().unwrap();
+        allocator.allocate_frame().unwrap();
+        allocator.allocate_frame().unwrap();
+        assert!(allocator.allocate_frame().is_none());
+        assert_eq!(allocator.allocated(), 3);
+
+        unsafe { allocator.deallocate_frame(frame) };
+        assert_eq!(allocator.freed(), 1);
+        assert_eq!(allocator.in_use(), 2);
+    }
+
+    #[test]
+    fn deallocate_range() {
+        let mut allocator = CountingFrameAllocator::new(BumpAllocator { next: 0, end: 0 });
+        let start = PhysFrame::containing_address(PhysAddr::new(0x1000));
+
+        unsafe { allocator.deallocate_range(PhysFrame::range(start, start + 3)) };
+        assert_eq!(allocator.freed(), 3);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn map_to_with_dyn_allocator() {
+        use crate::structures::paging::test_util::TableAllocator;
+        use crate::structures::paging::{Mapper, OffsetPageTable, Page, PageTable, PageTableFlags};
+        use crate::VirtAddr;
+
+        let mut tables = [
+            PageTable::new(),
+            PageTable::new(),
+            PageTable::new(),
+            PageTable::new(),
+        ];
+        let (level_4_table, rest) = tables.split_first_mut().unwrap();
+        let mut allocator = TableAllocator(rest.iter_mut());
+        let allocator: &mut dyn FrameAllocator<Size4KiB> = &mut allocator;
+
+        // The page tables are accessed through their virtual addresses, so the offset is zero.
+        let mut mapper = unsafe { OffsetPageTable::new(level_4_table, VirtAddr::zero()) };
+        let page = Page::<Size4KiB>::containing_address(VirtAddr::new(0x1234_5000));
+        let frame = PhysFrame::containing_address(PhysAddr::new(0x1000));
+        let flags = PageTableFlags::PRESENT | PageTableFlags::WRITABLE;
+
+        // The flush is ignored because the page tables are not active.
+        unsafe { mapper.map_to(page, frame, flags, allocator) }
+            .unwrap()
+            .ignore();
+        assert_eq!(mapper.translate_page(page).unwrap(), frame);
+        assert!(allocator.allocate_frame().is_none());
+    }
 }
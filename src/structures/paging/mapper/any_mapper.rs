@@ -0,0 +1,159 @@
+use crate::structures::paging::mapper::*;
+
+/// A mapper that is either an [`OffsetPageTable`] or a [`RecursivePageTable`], chosen at
+/// runtime.
+///
+/// This is useful for kernels that support both ways of accessing page table frames, e.g.
+/// depending on what the bootloader provides. All trait methods are forwarded to the wrapped
+/// mapper. This requires a `match` on every call, which is cheap compared to the page table
+/// walk itself, but prevents the compiler from specializing the calling code for a single
+/// mapper type. Use the concrete types directly if the mapping scheme is known at compile time.
+#[derive(Debug)]
+pub enum AnyMapper<'a> {
+    /// The complete physical memory is mapped at some offset.
+    Offset(OffsetPageTable<'a>),
+    /// The level 4 table is recursively mapped.
+    Recursive(RecursivePageTable<'a>),
+}
+
+impl<'a> From<OffsetPageTable<'a>> for AnyMapper<'a> {
+    #[inline]
+    fn from(mapper: OffsetPageTable<'a>) -> Self {
+        AnyMapper::Offset(mapper)
+    }
+}
+
+impl<'a> From<RecursivePageTable<'a>> for AnyMapper<'a> {
+    #[inline]
+    fn from(mapper: RecursivePageTable<'a>) -> Self {
+        AnyMapper::Recursive(mapper)
+    }
+}
+
+/// Evaluates `$body` with `$inner` bound to the wrapped mapper.
+macro_rules! dispatch {
+    ($self:expr, $inner:ident => $body:expr) => {
+        match $self {
+            AnyMapper::Offset($inner) => $body,
+            AnyMapper::Recursive($inner) => $body,
+        }
+    };
+}
+
+impl<'a, S> Mapper<S> for AnyMapper<'a>
+where
+    S: PageSize,
+    OffsetPageTable<'a>: Mapper<S>,
+    RecursivePageTable<'a>: Mapper<S>,
+{
+    #[inline]
+    unsafe fn map_to_with_table_flags<A>(
+        &mut self,
+        page: Page<S>,
+        frame: PhysFrame<S>,
+        flags: PageTableFlags,
+        parent_table_flags: PageTableFlags,
+        allocator: &mut A,
+    ) -> Result<MapperFlush<S>, MapToError<S>>
+    where
+        A: FrameAllocator<Size4KiB> + ?Sized,
+    {
+        dispatch!(self, inner => unsafe {
+            inner.map_to_with_table_flags(page, frame, flags, parent_table_flags, allocator)
+        })
+    }
+
+    #[inline]
+    fn unmap(&mut self, page: Page<S>) -> Result<(PhysFrame<S>, MapperFlush<S>), UnmapError> {
+        dispatch!(self, inner => inner.unmap(page))
+    }
+
+    #[inline]
+    unsafe fn remap(
+        &mut self,
+        page: Page<S>,
+        frame: PhysFrame<S>,
+        flags: PageTableFlags,
+    ) -> Result<(PhysFrame<S>, MapperFlush<S>), UnmapError> {
+        dispatch!(self, inner => unsafe { inner.remap(page, frame, flags) })
+    }
+
+    #[inline]
+    unsafe fn update_flags(
+        &mut self,
+        page: Page<S>,
+        flags: PageTableFlags,
+    ) -> Result<MapperFlush<S>, FlagUpdateError> {
+        dispatch!(self, inner => unsafe { inner.update_flags(page, flags) })
+    }
+
+    #[inline]
+    unsafe fn set_flags_p4_entry(
+        &mut self,
+        page: Page<S>,
+        flags: PageTableFlags,
+    ) -> Result<MapperFlushAll, FlagUpdateError> {
+        dispatch!(self, inner => unsafe { inner.set_flags_p4_entry(page, flags) })
+    }
+
+    #[inline]
+    unsafe fn set_flags_p3_entry(
+        &mut self,
+        page: Page<S>,
+        flags: PageTableFlags,
+    ) -> Result<MapperFlushAll, FlagUpdateError> {
+        dispatch!(self, inner => unsafe { inner.set_flags_p3_entry(page, flags) })
+    }
+
+    #[inline]
+    unsafe fn set_flags_p2_entry(
+        &mut self,
+        page: Page<S>,
+        flags: PageTableFlags,
+    ) -> Result<MapperFlushAll, FlagUpdateError> {
+        dispatch!(self, inner => unsafe { inner.set_flags_p2_entry(page, flags) })
+    }
+
+    #[inline]
+    fn translate_page(&self, page: Page<S>) -> Result<PhysFrame<S>, TranslateError> {
+        dispatch!(self, inner => inner.translate_page(page))
+    }
+}
+
+impl Translate for AnyMapper<'_> {
+    #[inline]
+    fn translate(&self, addr: VirtAddr) -> TranslateResult {
+        dispatch!(self, inner => inner.translate(addr))
+    }
+
+    #[inline]
+    fn effective_flags(&self, addr: VirtAddr) -> Option<PageTableFlags> {
+        dispatch!(self, inner => inner.effective_flags(addr))
+    }
+
+    #[inline]
+    fn unmapped_size(&self, addr: VirtAddr) -> u64 {
+        dispatch!(self, inner => inner.unmapped_size(addr))
+    }
+}
+
+impl CleanUp for AnyMapper<'_> {
+    #[inline]
+    unsafe fn clean_up<D>(&mut self, frame_deallocator: &mut D)
+    where
+        D: FrameDeallocator<Size4KiB>,
+    {
+        dispatch!(self, inner => unsafe { inner.clean_up(frame_deallocator) })
+    }
+
+    #[inline]
+    unsafe fn clean_up_addr_range<D>(
+        &mut self,
+        range: PageRangeInclusive,
+        frame_deallocator: &mut D,
+    ) where
+        D: FrameDeallocator<Size4KiB>,
+    {
+        dispatch!(self, inner => unsafe { inner.clean_up_addr_range(range, frame_deallocator) })
+    }
+}
@@ -47,7 +47,253 @@ impl<'a, P: PageTableFrameMapping> MappedPageTable<'a, P> {
 
     /// Returns the `PageTableFrameMapping` used for converting virtual to physical addresses.
     pub fn page_table_frame_mapping(&self) -> &P {
-        &self.page_table_walker.page_table_frame_mapping
+        self.page_table_walker.page_table_frame_mapping()
+    }
+
+    /// Clears the `ACCESSED` flag of the level 1 entries of all pages in the given range.
+    ///
+    /// Pages that are not mapped or that are part of a huge page are skipped. The CPU only sets
+    /// the flag again once the cached entries are evicted from the TLB, so the returned
+    /// [`MapperFlushRange`] must be flushed before the flags are sampled again.
+    pub fn clear_accessed_range(&mut self, range: PageRange) -> MapperFlushRange<Size4KiB> {
+        for page in range {
+            let p4 = &mut self.level_4_table;
+            let p3 = match self
+                .page_table_walker
+                .next_table_mut(&mut p4[page.p4_index()])
+            {
+                Ok(p3) => p3,
+                Err(_) => continue,
+            };
+            let p2 = match self
+                .page_table_walker
+                .next_table_mut(&mut p3[page.p3_index()])
+            {
+                Ok(p2) => p2,
+                Err(_) => continue,
+            };
+            let p1 = match self
+                .page_table_walker
+                .next_table_mut(&mut p2[page.p2_index()])
+            {
+                Ok(p1) => p1,
+                Err(_) => continue,
+            };
+            p1[page.p1_index()].take_accessed();
+        }
+        MapperFlushRange::new(range)
+    }
+
+    /// Marks all writable pages in the given range as copy-on-write.
+    ///
+    /// For each mapped page in the range that has the `WRITABLE` flag set, the flag is cleared
+    /// and the given `cow_flag` is set instead. Writes to these pages then cause a page fault,
+    /// whose handler can detect the copy-on-write marker, copy the frame, and map the copy as
+    /// writable again. Unmapped pages and pages that are not writable are skipped, so that
+    /// writes to them still cause regular page faults.
+    ///
+    /// If a page in the range is part of a huge page, no entry is modified and
+    /// [`FlagUpdateError::ParentEntryHugePage`] is returned. Otherwise the returned
+    /// [`MapperFlushRange`] must be flushed, as the TLB might still contain writable entries.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `cow_flag` is empty or contains flags that are not available to the OS, i.e.
+    /// flags other than `BIT_9` to `BIT_11` and `BIT_52` to `BIT_62`.
+    ///
+    /// ## Safety
+    ///
+    /// Like [`Mapper::update_flags`], this function is unsafe because changing the flags of a
+    /// mapping can break memory safety, e.g. if code that writes to the pages doesn't expect
+    /// the resulting page faults.
+    pub unsafe fn mark_cow_range(
+        &mut self,
+        range: PageRange,
+        cow_flag: PageTableFlags,
+    ) -> Result<MapperFlushRange<Size4KiB>, FlagUpdateError> {
+        const AVAILABLE: PageTableFlags =
+            PageTableFlags::from_bits_truncate((0b111 << 9) | (0x7ff << 52));
+        assert!(
+            !cow_flag.is_empty() && AVAILABLE.contains(cow_flag),
+            "the copy-on-write marker must consist of flags available to the OS"
+        );
+
+        // Check for huge pages first, so that the range is either marked completely or not at
+        // all.
+        for page in range {
+            self.present_entry_4kib_mut(page)?;
+        }
+        for page in range {
+            if let Some(entry) = self.present_entry_4kib_mut(page)? {
+                let flags = entry.level_1_flags();
+                if flags.contains(PageTableFlags::WRITABLE) {
+                    entry.set_level_1_flags((flags - PageTableFlags::WRITABLE) | cow_flag);
+                }
+            }
+        }
+        Ok(MapperFlushRange::new(range))
+    }
+
+    /// Returns the level 1 entry of the given page if it is present.
+    fn present_entry_4kib_mut(
+        &mut self,
+        page: Page,
+    ) -> Result<Option<&mut PageTableEntry>, FlagUpdateError> {
+        let p4 = &mut self.level_4_table;
+        let p3 = match self
+            .page_table_walker
+            .next_table_mut(&mut p4[page.p4_index()])
+        {
+            Ok(p3) => p3,
+            Err(PageTableWalkError::NotMapped) => return Ok(None),
+            Err(PageTableWalkError::MappedToHugePage) => {
+                return Err(FlagUpdateError::ParentEntryHugePage)
+            }
+        };
+        let p2 = match self
+            .page_table_walker
+            .next_table_mut(&mut p3[page.p3_index()])
+        {
+            Ok(p2) => p2,
+            Err(PageTableWalkError::NotMapped) => return Ok(None),
+            Err(PageTableWalkError::MappedToHugePage) => {
+                return Err(FlagUpdateError::ParentEntryHugePage)
+            }
+        };
+        let p1 = match self
+            .page_table_walker
+            .next_table_mut(&mut p2[page.p2_index()])
+        {
+            Ok(p1) => p1,
+            Err(PageTableWalkError::NotMapped) => return Ok(None),
+            Err(PageTableWalkError::MappedToHugePage) => {
+                return Err(FlagUpdateError::ParentEntryHugePage)
+            }
+        };
+        let entry = &mut p1[page.p1_index()];
+        if entry.flags().contains(PageTableFlags::PRESENT) {
+            Ok(Some(entry))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Returns a reference to the leaf page table entry that maps the given virtual address.
+    ///
+    /// If the address is part of a huge page, the level 3 or level 2 entry of that huge page is
+    /// returned. Returns `None` if one of the parent entries is unused or if the leaf entry is
+    /// unused. Note that the returned entry is not necessarily present, e.g. if the OS stores
+    /// swap information in a non-present entry.
+    ///
+    /// Bit 7 of a level 1 entry is the PAT bit, so it looks like a huge page entry. Use
+    /// [`Translate::translate`] to find out the page size and the `level_1_*` methods of
+    /// [`PageTableEntry`] to read a level 1 entry.
+    pub fn entry_for(&self, addr: VirtAddr) -> Option<&PageTableEntry> {
+        let p4 = &self.level_4_table;
+        let p3 = self
+            .page_table_walker
+            .next_table(&p4[addr.p4_index()])
+            .ok()?;
+        let p3_entry = &p3[addr.p3_index()];
+        let p2 = match self.page_table_walker.next_table(p3_entry) {
+            Ok(page_table) => page_table,
+            Err(PageTableWalkError::NotMapped) => return None,
+            Err(PageTableWalkError::MappedToHugePage) => return Some(p3_entry),
+        };
+        let p2_entry = &p2[addr.p2_index()];
+        let p1 = match self.page_table_walker.next_table(p2_entry) {
+            Ok(page_table) => page_table,
+            Err(PageTableWalkError::NotMapped) => return None,
+            Err(PageTableWalkError::MappedToHugePage) => return Some(p2_entry),
+        };
+        let p1_entry = &p1[addr.p1_index()];
+        if p1_entry.is_unused() {
+            return None;
+        }
+        Some(p1_entry)
+    }
+
+    /// Returns a mutable reference to the leaf page table entry that maps the given virtual
+    /// address.
+    ///
+    /// This is the mutable variant of [`entry_for`](Self::entry_for). Note that changes to the
+    /// entry are not flushed from the TLB automatically.
+    pub fn entry_for_mut(&mut self, addr: VirtAddr) -> Option<&mut PageTableEntry> {
+        const PRESENT_HUGE_PAGE: PageTableFlags =
+            PageTableFlags::PRESENT.union(PageTableFlags::HUGE_PAGE);
+
+        let p4 = &mut self.level_4_table;
+        let p3 = self
+            .page_table_walker
+            .next_table_mut(&mut p4[addr.p4_index()])
+            .ok()?;
+        let p3_entry = &mut p3[addr.p3_index()];
+        if p3_entry.flags().contains(PRESENT_HUGE_PAGE) {
+            return Some(p3_entry);
+        }
+        let p2 = self.page_table_walker.next_table_mut(p3_entry).ok()?;
+        let p2_entry = &mut p2[addr.p2_index()];
+        if p2_entry.flags().contains(PRESENT_HUGE_PAGE) {
+            return Some(p2_entry);
+        }
+        let p1 = self.page_table_walker.next_table_mut(p2_entry).ok()?;
+        let p1_entry = &mut p1[addr.p1_index()];
+        if p1_entry.is_unused() {
+            return None;
+        }
+        Some(p1_entry)
+    }
+
+    /// Translates the given virtual address and returns the frames of the page tables that were
+    /// visited on the way.
+    ///
+    /// The translation result is the same as the one returned by
+    /// [`translate`](Translate::translate). The returned array contains the frames of the level
+    /// 4, level 3, level 2, and level 1 tables, in that order. Since the mapper only knows the
+    /// virtual address of the level 4 table, its frame must be passed as `level_4_frame`.
+    /// Tables that were not visited, because the walk stopped early at a huge page or an unused
+    /// entry, are `None`.
+    ///
+    /// This is useful for debugging, e.g. to show the chain of page tables for an address.
+    pub fn translate_with_path(
+        &self,
+        addr: VirtAddr,
+        level_4_frame: PhysFrame,
+    ) -> (TranslateResult, [Option<PhysFrame>; 4]) {
+        let mut path = [Some(level_4_frame), None, None, None];
+        let mut table: &PageTable = self.level_4_table;
+        let indices = [addr.p4_index(), addr.p3_index(), addr.p2_index()];
+        for (frame, index) in path[1..].iter_mut().zip(indices) {
+            let entry = &table[index];
+            match self.page_table_walker.next_table(entry) {
+                Ok(next_table) => {
+                    *frame = entry.frame().ok();
+                    table = next_table;
+                }
+                Err(_) => break,
+            }
+        }
+        (self.translate(addr), path)
+    }
+
+    /// Returns an iterator over the frames of all page tables that are reachable from the level
+    /// 4 table, including the level 4 table itself.
+    ///
+    /// This is useful for freeing an entire address space after all pages are unmapped. Since
+    /// the mapper only knows the virtual address of the level 4 table, its frame must be passed
+    /// as `level_4_frame`. Huge page entries don't point to a page table, so they are skipped.
+    ///
+    /// Each table is yielded after all tables below it, so a table has been read completely
+    /// when its frame is returned, and `level_4_frame` is yielded last. Every frame is yielded
+    /// only once, even if the table is referenced by multiple entries, e.g. through a recursive
+    /// entry. Checking for earlier references requires walking the tables visited so far again
+    /// for every table, so this is quadratic in the number of page tables.
+    pub fn table_frames(&self, level_4_frame: PhysFrame) -> impl Iterator<Item = PhysFrame> + '_ {
+        TableFrames {
+            page_table_walker: &self.page_table_walker,
+            stack: [(&*self.level_4_table, 0, Some(level_4_frame)); 4],
+            depth: 1,
+        }
     }
 
     /// Helper function for implementing Mapper. Safe to limit the scope of unsafe, see
@@ -144,7 +390,7 @@ impl<'a, P: PageTableFrameMapping> MappedPageTable<'a, P> {
         if !p1[page.p1_index()].is_unused() {
             return Err(MapToError::PageAlreadyMapped(frame));
         }
-        p1[page.p1_index()].set_frame(frame, flags);
+        p1[page.p1_index()].set_level_1_frame(frame, flags);
 
         Ok(MapperFlush::new(page))
     }
@@ -163,6 +409,7 @@ impl<P: PageTableFrameMapping> Mapper<Size1GiB> for MappedPageTable<'_, P> {
     where
         A: FrameAllocator<Size4KiB> + ?Sized,
     {
+        debug_assert_user_parent_flags(flags, parent_table_flags);
         self.map_to_1gib(page, frame, flags, parent_table_flags, allocator)
     }
 
@@ -192,6 +439,34 @@ impl<P: PageTableFrameMapping> Mapper<Size1GiB> for MappedPageTable<'_, P> {
         Ok((frame, MapperFlush::new(page)))
     }
 
+    unsafe fn remap(
+        &mut self,
+        page: Page<Size1GiB>,
+        frame: PhysFrame<Size1GiB>,
+        flags: PageTableFlags,
+    ) -> Result<(PhysFrame<Size1GiB>, MapperFlush<Size1GiB>), UnmapError> {
+        let p4 = &mut self.level_4_table;
+        let p3 = self
+            .page_table_walker
+            .next_table_mut(&mut p4[page.p4_index()])?;
+
+        let p3_entry = &mut p3[page.p3_index()];
+        let entry_flags = p3_entry.flags();
+
+        if !entry_flags.contains(PageTableFlags::PRESENT) {
+            return Err(UnmapError::PageNotMapped);
+        }
+        if !entry_flags.contains(PageTableFlags::HUGE_PAGE) {
+            return Err(UnmapError::ParentEntryHugePage);
+        }
+
+        let old_frame = PhysFrame::from_start_address(p3_entry.addr())
+            .map_err(|AddressNotAligned| UnmapError::InvalidFrameAddress(p3_entry.addr()))?;
+
+        p3_entry.set_addr(frame.start_address(), flags | PageTableFlags::HUGE_PAGE);
+        Ok((old_frame, MapperFlush::new(page)))
+    }
+
     unsafe fn update_flags(
         &mut self,
         page: Page<Size1GiB>,
@@ -271,6 +546,7 @@ impl<P: PageTableFrameMapping> Mapper<Size2MiB> for MappedPageTable<'_, P> {
     where
         A: FrameAllocator<Size4KiB> + ?Sized,
     {
+        debug_assert_user_parent_flags(flags, parent_table_flags);
         self.map_to_2mib(page, frame, flags, parent_table_flags, allocator)
     }
 
@@ -303,6 +579,37 @@ impl<P: PageTableFrameMapping> Mapper<Size2MiB> for MappedPageTable<'_, P> {
         Ok((frame, MapperFlush::new(page)))
     }
 
+    unsafe fn remap(
+        &mut self,
+        page: Page<Size2MiB>,
+        frame: PhysFrame<Size2MiB>,
+        flags: PageTableFlags,
+    ) -> Result<(PhysFrame<Size2MiB>, MapperFlush<Size2MiB>), UnmapError> {
+        let p4 = &mut self.level_4_table;
+        let p3 = self
+            .page_table_walker
+            .next_table_mut(&mut p4[page.p4_index()])?;
+        let p2 = self
+            .page_table_walker
+            .next_table_mut(&mut p3[page.p3_index()])?;
+
+        let p2_entry = &mut p2[page.p2_index()];
+        let entry_flags = p2_entry.flags();
+
+        if !entry_flags.contains(PageTableFlags::PRESENT) {
+            return Err(UnmapError::PageNotMapped);
+        }
+        if !entry_flags.contains(PageTableFlags::HUGE_PAGE) {
+            return Err(UnmapError::ParentEntryHugePage);
+        }
+
+        let old_frame = PhysFrame::from_start_address(p2_entry.addr())
+            .map_err(|AddressNotAligned| UnmapError::InvalidFrameAddress(p2_entry.addr()))?;
+
+        p2_entry.set_addr(frame.start_address(), flags | PageTableFlags::HUGE_PAGE);
+        Ok((old_frame, MapperFlush::new(page)))
+    }
+
     unsafe fn update_flags(
         &mut self,
         page: Page<Size2MiB>,
@@ -399,6 +706,7 @@ impl<P: PageTableFrameMapping> Mapper<Size4KiB> for MappedPageTable<'_, P> {
     where
         A: FrameAllocator<Size4KiB> + ?Sized,
     {
+        debug_assert_user_parent_flags(flags, parent_table_flags);
         self.map_to_4kib(page, frame, flags, parent_table_flags, allocator)
     }
 
@@ -419,15 +727,41 @@ impl<P: PageTableFrameMapping> Mapper<Size4KiB> for MappedPageTable<'_, P> {
 
         let p1_entry = &mut p1[page.p1_index()];
 
-        let frame = p1_entry.frame().map_err(|err| match err {
-            FrameError::FrameNotPresent => UnmapError::PageNotMapped,
-            FrameError::HugeFrame => UnmapError::ParentEntryHugePage,
-        })?;
+        let frame = p1_entry
+            .level_1_frame()
+            .map_err(|_| UnmapError::PageNotMapped)?;
 
         p1_entry.set_unused();
         Ok((frame, MapperFlush::new(page)))
     }
 
+    unsafe fn remap(
+        &mut self,
+        page: Page<Size4KiB>,
+        frame: PhysFrame<Size4KiB>,
+        flags: PageTableFlags,
+    ) -> Result<(PhysFrame<Size4KiB>, MapperFlush<Size4KiB>), UnmapError> {
+        let p4 = &mut self.level_4_table;
+        let p3 = self
+            .page_table_walker
+            .next_table_mut(&mut p4[page.p4_index()])?;
+        let p2 = self
+            .page_table_walker
+            .next_table_mut(&mut p3[page.p3_index()])?;
+        let p1 = self
+            .page_table_walker
+            .next_table_mut(&mut p2[page.p2_index()])?;
+
+        let p1_entry = &mut p1[page.p1_index()];
+
+        let old_frame = p1_entry
+            .level_1_frame()
+            .map_err(|_| UnmapError::PageNotMapped)?;
+
+        p1_entry.set_level_1_frame(frame, flags);
+        Ok((old_frame, MapperFlush::new(page)))
+    }
+
     unsafe fn update_flags(
         &mut self,
         page: Page<Size4KiB>,
@@ -448,7 +782,7 @@ impl<P: PageTableFrameMapping> Mapper<Size4KiB> for MappedPageTable<'_, P> {
             return Err(FlagUpdateError::PageNotMapped);
         }
 
-        p1[page.p1_index()].set_flags(flags);
+        p1[page.p1_index()].set_level_1_flags(flags);
 
         Ok(MapperFlush::new(page))
     }
@@ -525,8 +859,9 @@ impl<P: PageTableFrameMapping> Mapper<Size4KiB> for MappedPageTable<'_, P> {
             return Err(TranslateError::PageNotMapped);
         }
 
-        PhysFrame::from_start_address(p1_entry.addr())
-            .map_err(|AddressNotAligned| TranslateError::InvalidFrameAddress(p1_entry.addr()))
+        PhysFrame::from_start_address(p1_entry.level_1_addr()).map_err(|AddressNotAligned| {
+            TranslateError::InvalidFrameAddress(p1_entry.level_1_addr())
+        })
     }
 }
 
@@ -580,18 +915,81 @@ impl<P: PageTableFrameMapping> Translate for MappedPageTable<'_, P> {
             return TranslateResult::NotMapped;
         }
 
-        let frame = match PhysFrame::from_start_address(p1_entry.addr()) {
+        let frame = match PhysFrame::from_start_address(p1_entry.level_1_addr()) {
             Ok(frame) => frame,
-            Err(AddressNotAligned) => return TranslateResult::InvalidFrameAddress(p1_entry.addr()),
+            Err(AddressNotAligned) => {
+                return TranslateResult::InvalidFrameAddress(p1_entry.level_1_addr())
+            }
         };
         let offset = u64::from(addr.page_offset());
-        let flags = p1_entry.flags();
+        let flags = p1_entry.level_1_flags();
         TranslateResult::Mapped {
             frame: MappedFrame::Size4KiB(frame),
             offset,
             flags,
         }
     }
+
+    fn unmapped_size(&self, addr: VirtAddr) -> u64 {
+        let p3 = match self
+            .page_table_walker
+            .next_table(&self.level_4_table[addr.p4_index()])
+        {
+            Ok(page_table) => page_table,
+            Err(PageTableWalkError::NotMapped) => {
+                return PageTableLevel::Four.entry_address_space_alignment()
+            }
+            Err(PageTableWalkError::MappedToHugePage) => return 0,
+        };
+        let p2 = match self.page_table_walker.next_table(&p3[addr.p3_index()]) {
+            Ok(page_table) => page_table,
+            Err(PageTableWalkError::NotMapped) => {
+                return PageTableLevel::Three.entry_address_space_alignment()
+            }
+            Err(PageTableWalkError::MappedToHugePage) => return 0,
+        };
+        let p1 = match self.page_table_walker.next_table(&p2[addr.p2_index()]) {
+            Ok(page_table) => page_table,
+            Err(PageTableWalkError::NotMapped) => {
+                return PageTableLevel::Two.entry_address_space_alignment()
+            }
+            Err(PageTableWalkError::MappedToHugePage) => return 0,
+        };
+
+        if p1[addr.p1_index()].is_unused() {
+            Size4KiB::SIZE
+        } else {
+            0
+        }
+    }
+
+    fn effective_flags(&self, addr: VirtAddr) -> Option<PageTableFlags> {
+        let p4_entry = &self.level_4_table[addr.p4_index()];
+        let flags = p4_entry.flags();
+        let p3 = self.page_table_walker.next_table(p4_entry).ok()?;
+
+        let p3_entry = &p3[addr.p3_index()];
+        let flags = restrict_flags(p3_entry.flags(), flags);
+        let p2 = match self.page_table_walker.next_table(p3_entry) {
+            Ok(page_table) => page_table,
+            Err(PageTableWalkError::NotMapped) => return None,
+            Err(PageTableWalkError::MappedToHugePage) => return Some(flags),
+        };
+
+        let p2_entry = &p2[addr.p2_index()];
+        let flags = restrict_flags(p2_entry.flags(), flags);
+        let p1 = match self.page_table_walker.next_table(p2_entry) {
+            Ok(page_table) => page_table,
+            Err(PageTableWalkError::NotMapped) => return None,
+            Err(PageTableWalkError::MappedToHugePage) => return Some(flags),
+        };
+
+        let p1_entry = &p1[addr.p1_index()];
+        if p1_entry.is_unused() {
+            return None;
+        }
+        Some(restrict_flags(p1_entry.level_1_flags(), flags))
+    }
 }
 
 impl<P: PageTableFrameMapping> CleanUp for MappedPageTable<'_, P> {
@@ -688,12 +1086,130 @@ impl<P: PageTableFrameMapping> CleanUp for MappedPageTable<'_, P> {
     }
 }
 
+/// The iterator returned by [`MappedPageTable::table_frames`].
+struct TableFrames<'a, P: PageTableFrameMapping> {
+    page_table_walker: &'a PageTableWalker<P>,
+    /// The tables on the current path, together with the index of the next entry to visit and
+    /// the frame of the table. Only the first `depth` elements are valid.
+    stack: [(&'a PageTable, usize, Option<PhysFrame>); 4],
+    depth: usize,
+}
+
+impl<P: PageTableFrameMapping> TableFrames<'_, P> {
+    /// Returns whether an entry that was visited before the current entry points to `frame`.
+    ///
+    /// This walks the tables again in the same order as `next`, up to the current entry. It
+    /// also descends into tables that `next` skipped as duplicates, which doesn't change the
+    /// result because their entries were already visited in the first copy of the table.
+    fn referenced_before(&self, frame: PhysFrame) -> bool {
+        let mut stack = [(self.stack[0].0, 0); 4];
+        let mut depth = 1;
+        // The number of tables at the start of `stack` that are on the path to the current entry.
+        let mut on_path = 1;
+        loop {
+            let (table, index) = stack[depth - 1];
+            if depth == on_path && index == self.stack[depth - 1].1 - 1 {
+                if depth == self.depth {
+                    return false;
+                }
+                // Follow the path to the current entry.
+                stack[depth - 1].1 += 1;
+                stack[depth] = (self.stack[depth].0, 0);
+                depth += 1;
+                on_path += 1;
+                continue;
+            }
+            if index == 512 {
+                depth -= 1;
+                continue;
+            }
+            stack[depth - 1].1 += 1;
+
+            let entry = &table[index];
+            let next_table = match self.page_table_walker.next_table(entry) {
+                Ok(next_table) => next_table,
+                Err(_) => continue,
+            };
+            if entry.frame().ok() == Some(frame) {
+                return true;
+            }
+            if depth < 3
+                && !stack[..depth]
+                    .iter()
+                    .any(|(table, _)| core::ptr::eq(*table, next_table))
+            {
+                stack[depth] = (next_table, 0);
+                depth += 1;
+            }
+        }
+    }
+}
+
+impl<P: PageTableFrameMapping> Iterator for TableFrames<'_, P> {
+    type Item = PhysFrame;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.depth > 0 {
+            let (table, index, frame) = &mut self.stack[self.depth - 1];
+            let table: &PageTable = table;
+            if *index == 512 {
+                let frame = *frame;
+                self.depth -= 1;
+                if frame.is_some() {
+                    return frame;
+                }
+                continue;
+            }
+            let entry = &table[*index];
+            *index += 1;
+
+            let next_table = match self.page_table_walker.next_table(entry) {
+                Ok(next_table) => next_table,
+                Err(_) => continue,
+            };
+            let next_frame = match entry.frame() {
+                Ok(next_frame) => next_frame,
+                Err(_) => continue,
+            };
+            if self.stack[..self.depth]
+                .iter()
+                .any(|(table, _, _)| core::ptr::eq(*table, next_table))
+                || self.referenced_before(next_frame)
+            {
+                continue;
+            }
+
+            // Level 1 tables don't point to other tables.
+            if self.depth == 3 {
+                return Some(next_frame);
+            }
+            self.stack[self.depth] = (next_table, 0, Some(next_frame));
+            self.depth += 1;
+        }
+        None
+    }
+}
+
+/// A helper for walking a page table hierarchy through a [`PageTableFrameMapping`].
+///
+/// This type powers [`MappedPageTable`] and [`OffsetPageTable`](super::OffsetPageTable). It can
+/// be used to build custom mappers on top of it, e.g. for walking guest page tables.
 #[derive(Debug)]
-struct PageTableWalker<P: PageTableFrameMapping> {
+pub struct PageTableWalker<P: PageTableFrameMapping> {
     page_table_frame_mapping: P,
 }
 
 impl<P: PageTableFrameMapping> PageTableWalker<P> {
+    /// Creates a new `PageTableWalker` that uses the passed `PageTableFrameMapping` to access
+    /// the page tables.
+    ///
+    /// ## Safety
+    ///
+    /// The caller must guarantee that the passed `page_table_frame_mapping` is correct for all
+    /// page tables that are walked. Also, the walked page table hierarchy must not contain
+    /// page tables that are referenced by multiple entries (e.g. through a recursive entry),
+    /// since the references returned by [`next_table_mut`](Self::next_table_mut) would alias
+    /// otherwise.
     #[inline]
     pub unsafe fn new(page_table_frame_mapping: P) -> Self {
         Self {
@@ -701,13 +1217,19 @@ impl<P: PageTableFrameMapping> PageTableWalker<P> {
         }
     }
 
-    /// Internal helper function to get a reference to the page table of the next level.
+    /// Returns the `PageTableFrameMapping` used for accessing the page tables.
+    #[inline]
+    pub fn page_table_frame_mapping(&self) -> &P {
+        &self.page_table_frame_mapping
+    }
+
+    /// Returns a reference to the page table of the next level.
     ///
     /// Returns `PageTableWalkError::NotMapped` if the entry is unused. Returns
     /// `PageTableWalkError::MappedToHugePage` if the `HUGE_PAGE` flag is set
     /// in the passed entry.
     #[inline]
-    fn next_table<'b>(
+    pub fn next_table<'b>(
         &self,
         entry: &'b PageTableEntry,
     ) -> Result<&'b PageTable, PageTableWalkError> {
@@ -719,13 +1241,13 @@ impl<P: PageTableFrameMapping> PageTableWalker<P> {
         Ok(page_table)
     }
 
-    /// Internal helper function to get a mutable reference to the page table of the next level.
+    /// Returns a mutable reference to the page table of the next level.
     ///
     /// Returns `PageTableWalkError::NotMapped` if the entry is unused. Returns
     /// `PageTableWalkError::MappedToHugePage` if the `HUGE_PAGE` flag is set
     /// in the passed entry.
     #[inline]
-    fn next_table_mut<'b>(
+    pub fn next_table_mut<'b>(
         &self,
         entry: &'b mut PageTableEntry,
     ) -> Result<&'b mut PageTable, PageTableWalkError> {
@@ -737,16 +1259,20 @@ impl<P: PageTableFrameMapping> PageTableWalker<P> {
         Ok(page_table)
     }
 
-    /// Internal helper function to create the page table of the next level if needed.
+    /// Creates the page table of the next level if needed.
     ///
     /// If the passed entry is unused, a new frame is allocated from the given allocator, zeroed,
-    /// and the entry is updated to that address. If the passed entry is already mapped, the next
-    /// table is returned directly.
+    /// and the entry is updated to that address. If the passed entry is already mapped, the
+    /// `insert_flags` are added to it and the next table is returned directly.
     ///
-    /// Returns `MapToError::FrameAllocationFailed` if the entry is unused and the allocator
-    /// returned `None`. Returns `MapToError::ParentEntryHugePage` if the `HUGE_PAGE` flag is set
-    /// in the passed entry.
-    fn create_next_table<'b, A>(
+    /// The allocated frame doesn't need to be zeroed by the allocator. It is cleared through
+    /// [`PageTable::init_at`] before the entry points to it, so a newly created table never
+    /// contains stale entries, not even temporarily.
+    ///
+    /// Returns `PageTableCreateError::FrameAllocationFailed` if the entry is unused and the
+    /// allocator returned `None`. Returns `PageTableCreateError::MappedToHugePage` if the
+    /// `HUGE_PAGE` flag is set in the passed entry.
+    pub fn create_next_table<'b, A>(
         &self,
         entry: &'b mut PageTableEntry,
         insert_flags: PageTableFlags,
@@ -755,46 +1281,44 @@ impl<P: PageTableFrameMapping> PageTableWalker<P> {
     where
         A: FrameAllocator<Size4KiB> + ?Sized,
     {
-        let created;
-
         if entry.is_unused() {
             if let Some(frame) = allocator.allocate_frame() {
+                let page_table_ptr = self.page_table_frame_mapping.frame_to_pointer(frame);
+                unsafe { PageTable::init_at(page_table_ptr) };
                 entry.set_frame(frame, insert_flags);
-                created = true;
             } else {
                 return Err(PageTableCreateError::FrameAllocationFailed);
             }
-        } else {
-            if !insert_flags.is_empty() && !entry.flags().contains(insert_flags) {
-                entry.set_flags(entry.flags() | insert_flags);
-            }
-            created = false;
+        } else if !insert_flags.is_empty() && !entry.flags().contains(insert_flags) {
+            entry.set_flags(entry.flags() | insert_flags);
         }
 
-        let page_table = match self.next_table_mut(entry) {
+        match self.next_table_mut(entry) {
             Err(PageTableWalkError::MappedToHugePage) => {
-                return Err(PageTableCreateError::MappedToHugePage);
+                Err(PageTableCreateError::MappedToHugePage)
             }
             Err(PageTableWalkError::NotMapped) => panic!("entry should be mapped at this point"),
-            Ok(page_table) => page_table,
-        };
-
-        if created {
-            page_table.zero();
+            Ok(page_table) => Ok(page_table),
         }
-        Ok(page_table)
     }
 }
 
-#[derive(Debug)]
-enum PageTableWalkError {
+/// An error indicating that [`PageTableWalker::next_table`] or
+/// [`PageTableWalker::next_table_mut`] failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageTableWalkError {
+    /// The entry doesn't have the `PRESENT` flag set.
     NotMapped,
+    /// The entry has the `HUGE_PAGE` flag set, so it maps a huge page instead of a page table.
     MappedToHugePage,
 }
 
-#[derive(Debug)]
-enum PageTableCreateError {
+/// An error indicating that [`PageTableWalker::create_next_table`] failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageTableCreateError {
+    /// The entry has the `HUGE_PAGE` flag set, so it maps a huge page instead of a page table.
     MappedToHugePage,
+    /// A frame for the new page table was needed, but the frame allocator returned `None`.
     FrameAllocationFailed,
 }
 
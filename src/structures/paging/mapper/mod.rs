@@ -1,6 +1,11 @@
 //! Abstractions for reading and modifying the mapping of pages.
 
-pub use self::mapped_page_table::{MappedPageTable, PageTableFrameMapping};
+#[cfg(all(feature = "instructions", target_arch = "x86_64"))]
+pub use self::any_mapper::AnyMapper;
+pub use self::mapped_page_table::{
+    MappedPageTable, PageTableCreateError, PageTableFrameMapping, PageTableWalkError,
+    PageTableWalker,
+};
 #[cfg(target_pointer_width = "64")]
 pub use self::offset_page_table::OffsetPageTable;
 #[cfg(all(feature = "instructions", target_arch = "x86_64"))]
@@ -8,12 +13,14 @@ pub use self::recursive_page_table::{InvalidPageTable, RecursivePageTable};
 
 use crate::structures::paging::{
     frame_alloc::{FrameAllocator, FrameDeallocator},
-    page::PageRangeInclusive,
-    page_table::PageTableFlags,
+    page::{PageRange, PageRangeInclusive},
+    page_table::{CacheType, PageTableFlags},
     Page, PageSize, PhysFrame, Size1GiB, Size2MiB, Size4KiB,
 };
 use crate::{PhysAddr, VirtAddr};
 
+#[cfg(all(feature = "instructions", target_arch = "x86_64"))]
+mod any_mapper;
 mod mapped_page_table;
 mod offset_page_table;
 #[cfg(all(feature = "instructions", target_arch = "x86_64"))]
@@ -48,6 +55,133 @@ pub trait Translate {
             TranslateResult::Mapped { frame, offset, .. } => Some(frame.start_address() + offset),
         }
     }
+
+    /// Returns the effective flags of the mapping of the given virtual address.
+    ///
+    /// Unlike the flags returned by [`translate`](Translate::translate), the effective flags
+    /// take the entries of all page table levels into account: `WRITABLE` and `USER_ACCESSIBLE`
+    /// are only set if they are set on every level, and `NO_EXECUTE` is set if it is set on any
+    /// level. All other flags are taken from the lowest-level entry.
+    ///
+    /// Returns `None` if there is no mapping for the given address.
+    ///
+    /// The provided implementation only returns the flags of the lowest-level entry. The mapper
+    /// types of this crate override it to walk all levels.
+    #[inline]
+    fn effective_flags(&self, addr: VirtAddr) -> Option<PageTableFlags> {
+        match self.translate(addr) {
+            TranslateResult::Mapped { flags, .. } => Some(flags),
+            TranslateResult::NotMapped | TranslateResult::InvalidFrameAddress(_) => None,
+        }
+    }
+
+    /// Returns the size of the unmapped region that contains the given virtual address.
+    ///
+    /// If `addr` is not mapped, this returns the size of the naturally aligned region around
+    /// `addr` that contains no mapping at all, depending on the level at which the translation
+    /// stops. For example, 1GiB is returned if the level 3 entry for `addr` is unused. Returns
+    /// `0` if `addr` is mapped.
+    ///
+    /// The provided implementation is based on [`translate`](Translate::translate), so it
+    /// returns at most 4KiB. The mapper types of this crate override it to report the actual
+    /// level.
+    #[inline]
+    fn unmapped_size(&self, addr: VirtAddr) -> u64 {
+        match self.translate(addr) {
+            TranslateResult::Mapped { .. } => 0,
+            TranslateResult::NotMapped | TranslateResult::InvalidFrameAddress(_) => Size4KiB::SIZE,
+        }
+    }
+
+    /// Returns the number of bytes in the given range that are mapped.
+    ///
+    /// Huge pages are counted with the part that overlaps the range. Unmapped regions are
+    /// skipped in steps of [`unmapped_size`](Translate::unmapped_size), so large gaps in the
+    /// address space only require a few translations. Mappings with an invalid frame address
+    /// are not counted.
+    fn mapped_bytes_in_range(&self, range: PageRange<Size4KiB>) -> u64 {
+        let end = range.end.start_address();
+        let mut addr = range.start.start_address();
+        let mut bytes = 0;
+
+        while addr < end {
+            let (size, mapped) = match self.translate(addr) {
+                TranslateResult::Mapped { frame, .. } => (frame.size(), true),
+                TranslateResult::NotMapped | TranslateResult::InvalidFrameAddress(_) => {
+                    (self.unmapped_size(addr).max(Size4KiB::SIZE), false)
+                }
+            };
+
+            // Regions never cross the non-canonical gap, but `end` might be above it.
+            let remaining_in_region = size - (addr.as_u64() & (size - 1));
+            let remaining_in_range = match VirtAddr::steps_between_u64(&addr, &end) {
+                Some(steps) => steps,
+                None => break,
+            };
+            let step = remaining_in_region.min(remaining_in_range);
+            if mapped {
+                bytes += step;
+            }
+
+            addr = match VirtAddr::forward_checked_u64(addr, step) {
+                Some(next) => next,
+                None => break,
+            };
+        }
+
+        bytes
+    }
+
+    /// Returns whether the given virtual address is mapped to a present frame.
+    #[inline]
+    fn is_mapped(&self, addr: VirtAddr) -> bool {
+        self.effective_flags(addr)
+            .map_or(false, |flags| flags.contains(PageTableFlags::PRESENT))
+    }
+
+    /// Returns whether the given virtual address can be read, or written if `write` is set,
+    /// with the effective permissions of its mapping.
+    ///
+    /// If `user` is set, the access is checked for userspace (i.e. ring 3), which requires the
+    /// `USER_ACCESSIBLE` flag. Note that supervisor writes to read-only pages are only prevented
+    /// if `Cr0Flags::WRITE_PROTECT` is set, and that supervisor accesses to user pages might
+    /// additionally be prevented by SMAP. This method doesn't take either into account.
+    #[inline]
+    fn is_accessible(&self, addr: VirtAddr, write: bool, user: bool) -> bool {
+        let mut required = PageTableFlags::PRESENT;
+        if write {
+            required |= PageTableFlags::WRITABLE;
+        }
+        if user {
+            required |= PageTableFlags::USER_ACCESSIBLE;
+        }
+        self.effective_flags(addr)
+            .map_or(false, |flags| flags.contains(required))
+    }
+
+    /// Returns whether instructions can be fetched from the given virtual address with the
+    /// effective permissions of its mapping.
+    ///
+    /// If `user` is set, the fetch is checked for userspace (i.e. ring 3). The `NO_EXECUTE`
+    /// flag only has an effect if `EferFlags::NO_EXECUTE_ENABLE` is set, which this method
+    /// assumes.
+    #[inline]
+    fn is_executable(&self, addr: VirtAddr, user: bool) -> bool {
+        self.is_accessible(addr, false, user)
+            && !self
+                .effective_flags(addr)
+                .map_or(true, |flags| flags.contains(PageTableFlags::NO_EXECUTE))
+    }
+}
+
+/// Restricts the flags of a page table entry by the flags of its parent entry.
+///
+/// `WRITABLE` and `USER_ACCESSIBLE` are only kept if they are set in the parent entry, and
+/// `NO_EXECUTE` is added if it is set in the parent entry.
+#[inline]
+fn restrict_flags(flags: PageTableFlags, parent_flags: PageTableFlags) -> PageTableFlags {
+    let inherited = PageTableFlags::WRITABLE | PageTableFlags::USER_ACCESSIBLE;
+    (flags - (inherited - parent_flags)) | (parent_flags & PageTableFlags::NO_EXECUTE)
 }
 
 /// The return value of the [`Translate::translate`] function.
@@ -208,6 +342,15 @@ pub trait Mapper<S: PageSize> {
     /// Depending on the used mapper implementation, the `PRESENT` and `WRITABLE` flags might
     /// be set for parent tables, even if they are not specified in `parent_table_flags`.
     ///
+    /// Newly created page tables are always zeroed by the mapper, so the frames returned by the
+    /// `allocator` don't need to be zeroed.
+    ///
+    /// The CPU only allows user mode (ring 3) accesses to a page if the `USER_ACCESSIBLE` flag is
+    /// set on all page table levels. So if `flags` contains `USER_ACCESSIBLE`,
+    /// `parent_table_flags` must contain it too. The mapper types of this crate panic in debug
+    /// builds, before changing any entries, if `flags` contains `USER_ACCESSIBLE` but
+    /// `parent_table_flags` doesn't.
+    ///
     /// ## Safety
     ///
     /// Creating page table mappings is a fundamentally unsafe operation because
@@ -284,6 +427,25 @@ pub trait Mapper<S: PageSize> {
     /// Note that no page tables or pages are deallocated.
     fn unmap(&mut self, page: Page<S>) -> Result<(PhysFrame<S>, MapperFlush<S>), UnmapError>;
 
+    /// Points an existing mapping to a new frame with new flags and returns the frame that
+    /// used to be mapped.
+    ///
+    /// Unlike an [`unmap`](Mapper::unmap) followed by a [`map_to`](Mapper::map_to), the page
+    /// table entry is updated in a single write, so the page is never unmapped in between. Since
+    /// all page tables already exist, no frames are allocated. The `HUGE_PAGE` flag is set
+    /// automatically for huge pages.
+    ///
+    /// ## Safety
+    ///
+    /// This method is unsafe for the same reasons as [`map_to`](Mapper::map_to): re-mapping an
+    /// in-use page to a different frame changes all values stored in that page.
+    unsafe fn remap(
+        &mut self,
+        page: Page<S>,
+        frame: PhysFrame<S>,
+        flags: PageTableFlags,
+    ) -> Result<(PhysFrame<S>, MapperFlush<S>), UnmapError>;
+
     /// Updates the flags of an existing mapping.
     ///
     /// To read the current flags of a mapped page, use the [`Translate::translate`] method.
@@ -301,6 +463,26 @@ pub trait Mapper<S: PageSize> {
         flags: PageTableFlags,
     ) -> Result<MapperFlush<S>, FlagUpdateError>;
 
+    /// Updates the flags of an existing mapping and immediately flushes the page from the TLB.
+    ///
+    /// This is a convenience function that invokes [`Mapper::update_flags`] and calls
+    /// [`MapperFlush::flush`] on the result. Callers that update many pages should use
+    /// [`Mapper::update_flags`] instead and batch the TLB flushes.
+    ///
+    /// ## Safety
+    ///
+    /// This method is unsafe because changing the flags of a mapping might result in undefined
+    /// behavior, in the same ways as for [`Mapper::update_flags`].
+    #[cfg(all(feature = "instructions", target_arch = "x86_64"))]
+    #[inline]
+    unsafe fn update_flags_and_flush(
+        &mut self,
+        page: Page<S>,
+        flags: PageTableFlags,
+    ) -> Result<(), FlagUpdateError> {
+        unsafe { self.update_flags(page, flags) }.map(MapperFlush::flush)
+    }
+
     /// Set the flags of an existing page level 4 table entry
     ///
     /// ## Safety
@@ -374,6 +556,266 @@ pub trait Mapper<S: PageSize> {
         let page = Page::containing_address(VirtAddr::new(frame.start_address().as_u64()));
         unsafe { self.map_to(page, frame, flags, frame_allocator) }
     }
+
+    /// Maps a stack of `pages` pages directly below `top`, protected by an unmapped guard page.
+    ///
+    /// The stack occupies the pages from `top - pages` up to, but excluding, `top`, so the
+    /// initial stack pointer is `top.start_address()`. Each stack page is mapped to a new frame
+    /// from `frame_allocator`. The page directly below the stack, i.e. `top - pages - 1`, is
+    /// left unmapped as a guard page: a stack overflow touches this page and causes a page fault
+    /// instead of silently overwriting the memory below the stack. Note that a function with a
+    /// stack frame larger than a page can skip over the guard page.
+    ///
+    /// The returned [`MapperFlushRange`] covers the stack pages, so
+    /// [`pages`](MapperFlushRange::pages) returns the usable stack range.
+    ///
+    /// Returns [`MapToError::PageAlreadyMapped`] without mapping or allocating anything if the
+    /// guard page or one of the stack pages is already mapped. If mapping a stack page still
+    /// fails, e.g. because no frame for a new page table could be allocated, the stack pages
+    /// mapped so far are unmapped again and all stack frames are returned to `frame_allocator`.
+    /// Page tables that were created for the stack are not freed, see [`CleanUp`] for that.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the stack and the guard page don't fit below `top`.
+    ///
+    /// ## Safety
+    ///
+    /// The new stack mappings must uphold the same requirements as mappings created through
+    /// [`Mapper::map_to`]. In particular, the caller must ensure that the stack pages are not
+    /// used for anything else.
+    unsafe fn map_stack<A>(
+        &mut self,
+        top: Page<S>,
+        pages: u64,
+        flags: PageTableFlags,
+        frame_allocator: &mut A,
+    ) -> Result<MapperFlushRange<S>, MapToError<S>>
+    where
+        Self: Sized,
+        A: FrameAllocator<Size4KiB> + FrameAllocator<S> + FrameDeallocator<S> + ?Sized,
+    {
+        let bottom = top
+            .checked_sub(pages)
+            .expect("the stack doesn't fit below `top`");
+        let guard = bottom
+            .checked_sub(1)
+            .expect("the guard page doesn't fit below the stack");
+        // Check all pages up front, so that no frames are allocated for a failing call.
+        for page in Page::range(guard, top) {
+            match self.translate_page(page) {
+                Err(TranslateError::PageNotMapped) => {}
+                Err(TranslateError::ParentEntryHugePage) => {
+                    return Err(MapToError::ParentEntryHugePage)
+                }
+                Err(TranslateError::InvalidFrameAddress(addr)) => {
+                    return Err(MapToError::InvalidFrameAddress(addr))
+                }
+                Ok(frame) => return Err(MapToError::PageAlreadyMapped(frame)),
+            }
+        }
+
+        let stack = Page::range(bottom, top);
+        for page in stack {
+            let result = match FrameAllocator::<S>::allocate_frame(frame_allocator) {
+                Some(frame) => {
+                    unsafe { self.map_to(page, frame, flags, frame_allocator) }.map_err(|err| {
+                        unsafe { frame_allocator.deallocate_frame(frame) };
+                        err
+                    })
+                }
+                None => Err(MapToError::FrameAllocationFailed),
+            };
+            match result {
+                // The complete range is flushed through the returned `MapperFlushRange`.
+                Ok(flush) => flush.ignore(),
+                Err(err) => {
+                    // The stack pages were unmapped before and haven't been accessed since, so
+                    // they can't be cached in the TLB and don't need to be flushed.
+                    for mapped in Page::range(bottom, page) {
+                        if let Ok((frame, flush)) = self.unmap(mapped) {
+                            flush.ignore();
+                            unsafe { frame_allocator.deallocate_frame(frame) };
+                        }
+                    }
+                    return Err(err);
+                }
+            }
+        }
+        Ok(MapperFlushRange::new(stack))
+    }
+
+    /// Creates a new mapping with the given memory type.
+    ///
+    /// This behaves like [`Mapper::map_to`], but replaces the `WRITE_THROUGH` and `NO_CACHE`
+    /// bits and the PAT bit of `flags` with the bits that select the given [`CacheType`] for
+    /// the page size `S`. The `IA32_PAT` MSR must be set up with the layout described in the
+    /// [`CacheType`] documentation before using [`CacheType::WriteCombining`], e.g. for
+    /// write-combining framebuffers.
+    ///
+    /// ## Safety
+    ///
+    /// This method is unsafe because the caller must ensure that the new mapping upholds the
+    /// requirements of [`Mapper::map_to`]. Additionally, the frame must not be mapped with a
+    /// different memory type through any other page, e.g. an identity mapping of all physical
+    /// memory. The processor doesn't keep such aliases coherent, which can result in lost
+    /// writes or stale reads.
+    #[inline]
+    unsafe fn map_to_with_cache<A>(
+        &mut self,
+        page: Page<S>,
+        frame: PhysFrame<S>,
+        cache: CacheType,
+        flags: PageTableFlags,
+        frame_allocator: &mut A,
+    ) -> Result<MapperFlush<S>, MapToError<S>>
+    where
+        Self: Sized,
+        A: FrameAllocator<Size4KiB> + ?Sized,
+    {
+        let cache_flags = PageTableFlags::WRITE_THROUGH
+            | PageTableFlags::NO_CACHE
+            | PageTableFlags::HUGE_PAGE
+            | PageTableFlags::HUGE_PAGE_PAT;
+        let flags = (flags - cache_flags) | cache.flags::<S>();
+        unsafe { self.map_to(page, frame, flags, frame_allocator) }
+    }
+}
+
+/// Maps the physical region starting at `phys` with length `len` to the virtual region
+/// starting at `virt`, using the largest page size possible for each part of the region.
+///
+/// 1GiB and 2MiB pages are used wherever both the virtual and the physical address are
+/// aligned to the page size and the remaining length is large enough. All other parts of
+/// the region are mapped using 4KiB pages. This minimizes the number of page table entries,
+/// e.g. when mapping the complete physical address space at some offset.
+///
+/// 1GiB pages are only used if the current CPU supports them, as reported by
+#[cfg_attr(
+    all(feature = "instructions", target_arch = "x86_64"),
+    doc = "[`cpu::supports_1gib_pages`](crate::instructions::cpu::supports_1gib_pages)."
+)]
+#[cfg_attr(
+    not(all(feature = "instructions", target_arch = "x86_64")),
+    doc = "`cpu::supports_1gib_pages`. Without the `instructions` feature, support is assumed."
+)]
+///
+/// The `HUGE_PAGE` flag is set automatically for huge pages, so it should not be included
+/// in `flags`. Since a potentially large number of pages is mapped, a [`MapperFlushAll`] is
+/// returned instead of individual flush promises.
+///
+/// If the mapping fails, the region is left partially mapped. A
+/// [`MapToError::PageAlreadyMapped`] error for a huge page reports the first 4KiB frame of
+/// the huge frame that should have been mapped.
+///
+/// Panics if `virt`, `phys`, or `len` are not aligned to 4KiB.
+///
+/// ## Safety
+///
+/// This is a convenience function that invokes [`Mapper::map_to`] internally, so
+/// all safety requirements of it also apply for this function.
+///
+/// # Example
+///
+/// ```
+/// # use x86_64::{PhysAddr, VirtAddr};
+/// # use x86_64::structures::paging::{
+/// #     mapper::{map_region_auto, MapperAllSizes},
+/// #     FrameAllocator, PageTableFlags, Size4KiB,
+/// # };
+/// # unsafe fn test(mapper: &mut impl MapperAllSizes, frame_allocator: &mut impl FrameAllocator<Size4KiB>) {
+/// // map the first 64GiB of physical memory at offset 0xffff_8000_0000_0000
+/// let flags = PageTableFlags::PRESENT | PageTableFlags::WRITABLE;
+/// map_region_auto(
+///     mapper,
+///     VirtAddr::new(0xffff_8000_0000_0000),
+///     PhysAddr::new(0),
+///     64 << 30,
+///     flags,
+///     frame_allocator,
+/// )
+/// .unwrap()
+/// .ignore();
+/// # }
+/// ```
+pub unsafe fn map_region_auto<M, A>(
+    mapper: &mut M,
+    virt: VirtAddr,
+    phys: PhysAddr,
+    len: u64,
+    flags: PageTableFlags,
+    frame_allocator: &mut A,
+) -> Result<MapperFlushAll, MapToError<Size4KiB>>
+where
+    M: MapperAllSizes,
+    A: FrameAllocator<Size4KiB> + ?Sized,
+{
+    assert!(
+        virt.is_aligned(Size4KiB::SIZE),
+        "virtual address must be 4KiB aligned"
+    );
+    assert!(
+        phys.is_aligned(Size4KiB::SIZE),
+        "physical address must be 4KiB aligned"
+    );
+    assert!(
+        len % Size4KiB::SIZE == 0,
+        "length must be a multiple of 4KiB"
+    );
+
+    #[cfg(all(feature = "instructions", target_arch = "x86_64"))]
+    let use_1gib_pages = crate::instructions::cpu::supports_1gib_pages();
+    #[cfg(not(all(feature = "instructions", target_arch = "x86_64")))]
+    let use_1gib_pages = true;
+
+    let mut offset = 0;
+    while offset < len {
+        let virt = virt + offset;
+        let phys = phys + offset;
+        let remaining = len - offset;
+
+        if use_1gib_pages
+            && remaining >= Size1GiB::SIZE
+            && virt.is_aligned(Size1GiB::SIZE)
+            && phys.is_aligned(Size1GiB::SIZE)
+        {
+            let page = Page::<Size1GiB>::containing_address(virt);
+            let frame = PhysFrame::<Size1GiB>::containing_address(phys);
+            unsafe { mapper.map_to(page, frame, flags, frame_allocator) }
+                .map_err(MapToError::into_4kib)?
+                .ignore();
+            offset += Size1GiB::SIZE;
+        } else if remaining >= Size2MiB::SIZE
+            && virt.is_aligned(Size2MiB::SIZE)
+            && phys.is_aligned(Size2MiB::SIZE)
+        {
+            let page = Page::<Size2MiB>::containing_address(virt);
+            let frame = PhysFrame::<Size2MiB>::containing_address(phys);
+            unsafe { mapper.map_to(page, frame, flags, frame_allocator) }
+                .map_err(MapToError::into_4kib)?
+                .ignore();
+            offset += Size2MiB::SIZE;
+        } else {
+            let page = Page::<Size4KiB>::containing_address(virt);
+            let frame = PhysFrame::<Size4KiB>::containing_address(phys);
+            unsafe { mapper.map_to(page, frame, flags, frame_allocator) }?.ignore();
+            offset += Size4KiB::SIZE;
+        }
+    }
+
+    Ok(MapperFlushAll::new())
+}
+
+/// Panics in debug builds if the leaf `flags` contain `USER_ACCESSIBLE`, but the
+/// `parent_table_flags` don't.
+#[inline]
+fn debug_assert_user_parent_flags(flags: PageTableFlags, parent_table_flags: PageTableFlags) {
+    debug_assert!(
+        !flags.contains(PageTableFlags::USER_ACCESSIBLE)
+            || parent_table_flags.contains(PageTableFlags::USER_ACCESSIBLE),
+        "the mapping is USER_ACCESSIBLE, but `parent_table_flags` are not, so the page might \
+         not be accessible from user mode"
+    );
 }
 
 /// This type represents a page whose mapping has changed in the page table.
@@ -417,6 +859,46 @@ impl<S: PageSize> MapperFlush<S> {
     }
 }
 
+/// This type represents a change of the page table entries of a range of pages requiring a TLB
+/// flush for each page in the range.
+///
+/// The old entries might be still cached in the translation lookaside buffer (TLB), so they need
+/// to be flushed from the TLB before they're accessed. This type is returned from a function that
+/// changed the entries to ensure that the TLB flush is not forgotten.
+#[derive(Debug)]
+#[must_use = "Page Table changes must be flushed or ignored."]
+pub struct MapperFlushRange<S: PageSize>(PageRange<S>);
+
+impl<S: PageSize> MapperFlushRange<S> {
+    /// Create a new flush promise
+    ///
+    /// Note that this method is intended for implementing mapper types and no other uses are
+    /// expected.
+    #[inline]
+    pub fn new(pages: PageRange<S>) -> Self {
+        MapperFlushRange(pages)
+    }
+
+    /// Flush the pages from the TLB to ensure that the newest entries are used.
+    #[cfg(all(feature = "instructions", target_arch = "x86_64"))]
+    #[inline]
+    pub fn flush(self) {
+        for page in self.0 {
+            crate::instructions::tlb::flush(page.start_address());
+        }
+    }
+
+    /// Don't flush the TLB and silence the “must be used” warning.
+    #[inline]
+    pub fn ignore(self) {}
+
+    /// Returns the range of pages to be flushed.
+    #[inline]
+    pub fn pages(&self) -> PageRange<S> {
+        self.0
+    }
+}
+
 /// This type represents a change of a page table requiring a complete TLB flush
 ///
 /// The old mapping might be still cached in the translation lookaside buffer (TLB), so it needs
@@ -459,6 +941,23 @@ pub enum MapToError<S: PageSize> {
     ParentEntryHugePage,
     /// The given page is already mapped to a physical frame.
     PageAlreadyMapped(PhysFrame<S>),
+    /// The page table entry for the given page points to an invalid physical address.
+    InvalidFrameAddress(PhysAddr),
+}
+
+impl<S: PageSize> MapToError<S> {
+    /// Converts this error into a `MapToError` for 4KiB pages, replacing the frame of
+    /// `PageAlreadyMapped` with its first 4KiB frame.
+    fn into_4kib(self) -> MapToError<Size4KiB> {
+        match self {
+            MapToError::FrameAllocationFailed => MapToError::FrameAllocationFailed,
+            MapToError::ParentEntryHugePage => MapToError::ParentEntryHugePage,
+            MapToError::PageAlreadyMapped(frame) => {
+                MapToError::PageAlreadyMapped(PhysFrame::containing_address(frame.start_address()))
+            }
+            MapToError::InvalidFrameAddress(addr) => MapToError::InvalidFrameAddress(addr),
+        }
+    }
 }
 
 /// An error indicating that an `unmap` call failed.
@@ -538,3 +1037,368 @@ pub trait CleanUp {
     ) where
         D: FrameDeallocator<Size4KiB>;
 }
+
+#[cfg(all(test, target_pointer_width = "64"))]
+mod tests {
+    use super::*;
+    use crate::structures::paging::test_util::TableAllocator;
+    use crate::structures::paging::PageTable;
+
+    /// Accesses the page tables through their physical address.
+    #[derive(Debug)]
+    struct IdentityMapping;
+
+    unsafe impl PageTableFrameMapping for IdentityMapping {
+        fn frame_to_pointer(&self, frame: PhysFrame) -> *mut PageTable {
+            frame.start_address().as_u64() as *mut PageTable
+        }
+    }
+
+    fn range(start: u64, end: u64) -> PageRange {
+        Page::range(
+            Page::containing_address(VirtAddr::new(start)),
+            Page::containing_address(VirtAddr::new(end)),
+        )
+    }
+
+    fn tables<const N: usize>() -> [PageTable; N] {
+        [(); N].map(|_| PageTable::new())
+    }
+
+    fn frame_of(table: &PageTable) -> PhysFrame {
+        PhysFrame::containing_address(PhysAddr::new(table as *const _ as u64))
+    }
+
+    /// A mapper that uses the first of the given tables as level 4 table and allocates the
+    /// remaining ones for new page tables.
+    struct TestMapper<'a> {
+        mapper: MappedPageTable<'a, IdentityMapping>,
+        allocator: TableAllocator<'a>,
+    }
+
+    impl<'a> TestMapper<'a> {
+        fn new(tables: &'a mut [PageTable]) -> Self {
+            let (level_4_table, rest) = tables.split_first_mut().unwrap();
+            TestMapper {
+                mapper: unsafe { MappedPageTable::new(level_4_table, IdentityMapping) },
+                allocator: TableAllocator(rest.iter_mut()),
+            }
+        }
+
+        /// Maps the page at `addr` to the frame at `frame_addr`.
+        ///
+        /// The flush is ignored because the page tables are not active.
+        fn map<S: PageSize + core::fmt::Debug>(
+            &mut self,
+            addr: u64,
+            frame_addr: u64,
+            flags: PageTableFlags,
+        ) where
+            MappedPageTable<'a, IdentityMapping>: Mapper<S>,
+        {
+            let page = Page::<S>::containing_address(VirtAddr::new(addr));
+            let frame = PhysFrame::containing_address(PhysAddr::new(frame_addr));
+            unsafe { self.mapper.map_to(page, frame, flags, &mut self.allocator) }
+                .unwrap()
+                .ignore();
+        }
+    }
+
+    /// Wraps a [`TableAllocator`] and records the deallocated frames.
+    struct RecordingAllocator<'a, 'b> {
+        allocator: &'b mut TableAllocator<'a>,
+        deallocated: Vec<PhysFrame>,
+    }
+
+    impl<'a, 'b> RecordingAllocator<'a, 'b> {
+        fn new(allocator: &'b mut TableAllocator<'a>) -> Self {
+            RecordingAllocator {
+                allocator,
+                deallocated: Vec::new(),
+            }
+        }
+    }
+
+    unsafe impl FrameAllocator<Size4KiB> for RecordingAllocator<'_, '_> {
+        fn allocate_frame(&mut self) -> Option<PhysFrame> {
+            self.allocator.allocate_frame()
+        }
+    }
+
+    impl FrameDeallocator<Size4KiB> for RecordingAllocator<'_, '_> {
+        unsafe fn deallocate_frame(&mut self, frame: PhysFrame) {
+            self.deallocated.push(frame);
+        }
+    }
+
+    #[test]
+    fn mapped_bytes_in_range() {
+        let mut tables = tables::<5>();
+        let mut t = TestMapper::new(&mut tables);
+        let flags = PageTableFlags::PRESENT | PageTableFlags::WRITABLE;
+        t.map::<Size4KiB>(0x1000, 0x1000, flags);
+        t.map::<Size2MiB>(0x4000_0000, 0x20_0000, flags);
+        let mapper = &t.mapper;
+
+        assert_eq!(mapper.unmapped_size(VirtAddr::new(0x1000)), 0);
+        assert_eq!(mapper.unmapped_size(VirtAddr::new(0x2000)), Size4KiB::SIZE);
+        assert_eq!(
+            mapper.unmapped_size(VirtAddr::new(0x20_0000)),
+            Size2MiB::SIZE
+        );
+        assert_eq!(
+            mapper.unmapped_size(VirtAddr::new(0x8000_0000)),
+            Size1GiB::SIZE
+        );
+        assert_eq!(
+            mapper.unmapped_size(VirtAddr::new(0x80_0000_0000)),
+            512 * Size1GiB::SIZE
+        );
+
+        assert_eq!(
+            mapper.mapped_bytes_in_range(range(0, 0x8000_0000)),
+            Size4KiB::SIZE + Size2MiB::SIZE
+        );
+        assert_eq!(
+            mapper.mapped_bytes_in_range(range(0x4010_0000, 0x4030_0000)),
+            0x10_0000
+        );
+        assert_eq!(mapper.mapped_bytes_in_range(range(0x2000, 0x1000)), 0);
+        assert_eq!(
+            mapper.mapped_bytes_in_range(range(0, 0xffff_ffff_ffff_f000)),
+            Size4KiB::SIZE + Size2MiB::SIZE
+        );
+    }
+
+    #[test]
+    fn mark_cow_range() {
+        let mut tables = tables::<5>();
+        let mut t = TestMapper::new(&mut tables);
+        let writable = PageTableFlags::PRESENT | PageTableFlags::WRITABLE;
+        t.map::<Size4KiB>(0x1000, 0x1000, writable);
+        t.map::<Size4KiB>(0x2000, 0x2000, PageTableFlags::PRESENT);
+        t.map::<Size4KiB>(0x4000, 0x4000, writable);
+        t.map::<Size2MiB>(0x4000_0000, 0x20_0000, writable);
+        let mapper = &mut t.mapper;
+
+        let cow = PageTableFlags::BIT_9;
+        let result = unsafe { mapper.mark_cow_range(range(0x3000, 0x4020_0000), cow) };
+        assert!(matches!(result, Err(FlagUpdateError::ParentEntryHugePage)));
+        let flags = |mapper: &MappedPageTable<_>, addr| {
+            mapper.entry_for(VirtAddr::new(addr)).unwrap().flags()
+        };
+        assert_eq!(flags(mapper, 0x4000), writable);
+
+        unsafe { mapper.mark_cow_range(range(0, 0x5000), cow) }
+            .unwrap()
+            .ignore();
+        assert_eq!(flags(mapper, 0x1000), PageTableFlags::PRESENT | cow);
+        assert_eq!(flags(mapper, 0x2000), PageTableFlags::PRESENT);
+        assert_eq!(flags(mapper, 0x4000), PageTableFlags::PRESENT | cow);
+        assert!(mapper.entry_for(VirtAddr::new(0x3000)).is_none());
+    }
+
+    #[test]
+    fn table_frames() {
+        let mut tables = tables::<6>();
+        let level_4_frame = frame_of(&tables[0]);
+        let mut expected: Vec<_> = tables.iter().map(frame_of).collect();
+        expected.sort();
+        let mut t = TestMapper::new(&mut tables);
+        let flags = PageTableFlags::PRESENT | PageTableFlags::WRITABLE;
+        for addr in [0x1000, 0x2000, 0x40_0000] {
+            t.map::<Size4KiB>(addr, addr, flags);
+        }
+        t.map::<Size2MiB>(0x4000_0000, 0x20_0000, flags);
+
+        // A recursive entry must not cause the level 4 table to be walked again.
+        t.mapper.level_4_table_mut()[511].set_frame(level_4_frame, flags);
+        // A level 1 table that is shared by two level 2 entries must only be yielded once.
+        let shared_frame = frame_of(&tables[3]);
+        tables[2][5].set_frame(shared_frame, flags);
+        let t = TestMapper::new(&mut tables);
+
+        // The level 4 table, a level 3 table, a level 2 table and two level 1 tables for the
+        // 4KiB pages, and a level 2 table for the huge page.
+        let frames: Vec<_> = t.mapper.table_frames(level_4_frame).collect();
+        assert_eq!(frames.last(), Some(&level_4_frame));
+        let mut frames = frames;
+        frames.sort();
+        assert_eq!(frames, expected);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "might not be accessible from user mode")]
+    fn user_page_in_supervisor_table() {
+        let mut tables = tables::<4>();
+        let mut t = TestMapper::new(&mut tables);
+        let parent_flags = PageTableFlags::PRESENT | PageTableFlags::WRITABLE;
+        let flags = parent_flags | PageTableFlags::USER_ACCESSIBLE;
+
+        let page = Page::<Size4KiB>::containing_address(VirtAddr::new(0x1000));
+        let frame = PhysFrame::containing_address(PhysAddr::new(0x1000));
+        let _ = unsafe {
+            t.mapper
+                .map_to_with_table_flags(page, frame, flags, parent_flags, &mut t.allocator)
+        };
+    }
+
+    #[test]
+    fn map_stack() {
+        let mut tables = tables::<7>();
+        let mut t = TestMapper::new(&mut tables);
+        let flags = PageTableFlags::PRESENT | PageTableFlags::WRITABLE;
+
+        let top = Page::<Size4KiB>::containing_address(VirtAddr::new(0x10_0000));
+        let mut allocator = RecordingAllocator::new(&mut t.allocator);
+        let stack = unsafe { t.mapper.map_stack(top, 3, flags, &mut allocator) }.unwrap();
+        assert_eq!(stack.pages(), Page::range(top - 3, top));
+        stack.ignore();
+
+        assert!(t.mapper.translate_page(top - 1).is_ok());
+        assert!(t.mapper.translate_page(top - 3).is_ok());
+        assert!(t.mapper.translate_page(top - 4).is_err());
+        assert!(t.mapper.translate_page(top).is_err());
+
+        // The guard page of a stack directly above is the top page of the first stack.
+        let mut allocator = RecordingAllocator::new(&mut t.allocator);
+        let result = unsafe { t.mapper.map_stack(top + 1, 1, flags, &mut allocator) };
+        assert!(matches!(result, Err(MapToError::PageAlreadyMapped(_))));
+
+        // A stack overlapping the first one fails before allocating any frames.
+        let remaining = t.allocator.0.len();
+        let mut allocator = RecordingAllocator::new(&mut t.allocator);
+        let result = unsafe { t.mapper.map_stack(top - 2, 2, flags, &mut allocator) };
+        assert!(matches!(result, Err(MapToError::PageAlreadyMapped(_))));
+        assert_eq!(t.allocator.0.len(), remaining);
+    }
+
+    #[test]
+    fn map_stack_failure() {
+        let mut tables = tables::<6>();
+        let first_frame = frame_of(&tables[1]);
+        let last_frame = frame_of(&tables[5]);
+        let mut t = TestMapper::new(&mut tables);
+        let flags = PageTableFlags::PRESENT | PageTableFlags::WRITABLE;
+
+        // The first stack page uses one frame for itself and three for new page tables. The
+        // second page is in a new level 1 table, which can't be allocated after its frame.
+        let top = Page::<Size4KiB>::containing_address(VirtAddr::new(0x20_1000));
+        let mut allocator = RecordingAllocator::new(&mut t.allocator);
+        let result = unsafe { t.mapper.map_stack(top, 2, flags, &mut allocator) };
+        assert!(matches!(result, Err(MapToError::FrameAllocationFailed)));
+        assert_eq!(allocator.deallocated, [last_frame, first_frame]);
+        assert!(t.mapper.translate_page(top - 2).is_err());
+        assert!(t.mapper.translate_page(top - 1).is_err());
+    }
+
+    #[test]
+    fn translate_with_path() {
+        let mut tables = tables::<5>();
+        let [p4, p3, p2, p1, p2_huge] = [0, 1, 2, 3, 4].map(|i| frame_of(&tables[i]));
+        let mut t = TestMapper::new(&mut tables);
+        let flags = PageTableFlags::PRESENT | PageTableFlags::WRITABLE;
+        t.map::<Size4KiB>(0x1000, 0x5000, flags);
+        t.map::<Size2MiB>(0x4000_0000, 0x20_0000, flags);
+        let mapper = &t.mapper;
+
+        let (result, path) = mapper.translate_with_path(VirtAddr::new(0x1234), p4);
+        assert!(matches!(
+            result,
+            TranslateResult::Mapped { offset: 0x234, .. }
+        ));
+        assert_eq!(path, [Some(p4), Some(p3), Some(p2), Some(p1)]);
+
+        let (result, path) = mapper.translate_with_path(VirtAddr::new(0x4000_1000), p4);
+        assert!(matches!(
+            result,
+            TranslateResult::Mapped { offset: 0x1000, .. }
+        ));
+        assert_eq!(path, [Some(p4), Some(p3), Some(p2_huge), None]);
+
+        let (result, path) = mapper.translate_with_path(VirtAddr::new(0x80_0000_0000), p4);
+        assert!(matches!(result, TranslateResult::NotMapped));
+        assert_eq!(path, [Some(p4), None, None, None]);
+    }
+
+    #[test]
+    fn map_to_with_cache() {
+        let mut tables = tables::<5>();
+        let mut t = TestMapper::new(&mut tables);
+        let flags = PageTableFlags::PRESENT | PageTableFlags::WRITABLE;
+        let cache = CacheType::WriteCombining;
+
+        // Bit 12 of the frame address must not be confused with the PAT bit of huge pages.
+        let page = Page::<Size4KiB>::containing_address(VirtAddr::new(0x1000));
+        let frame = PhysFrame::containing_address(PhysAddr::new(0x3000));
+        unsafe {
+            t.mapper
+                .map_to_with_cache(page, frame, cache, flags, &mut t.allocator)
+        }
+        .unwrap()
+        .ignore();
+        match t.mapper.translate(page.start_address()) {
+            TranslateResult::Mapped {
+                frame: MappedFrame::Size4KiB(mapped),
+                flags: mapped_flags,
+                ..
+            } => {
+                assert_eq!(mapped, frame);
+                assert_eq!(mapped_flags, flags | cache.flags::<Size4KiB>());
+            }
+            other => panic!("unexpected translation {:?}", other),
+        }
+        let (unmapped, _) = t.mapper.unmap(page).unwrap();
+        assert_eq!(unmapped, frame);
+
+        let page = Page::<Size2MiB>::containing_address(VirtAddr::new(0x4000_0000));
+        let frame = PhysFrame::containing_address(PhysAddr::new(0x20_0000));
+        unsafe {
+            t.mapper
+                .map_to_with_cache(page, frame, cache, flags, &mut t.allocator)
+        }
+        .unwrap()
+        .ignore();
+        match t.mapper.translate(page.start_address()) {
+            TranslateResult::Mapped {
+                frame: MappedFrame::Size2MiB(mapped),
+                flags: mapped_flags,
+                ..
+            } => {
+                assert_eq!(mapped, frame);
+                assert_eq!(
+                    mapped_flags,
+                    flags | PageTableFlags::HUGE_PAGE | cache.flags::<Size2MiB>()
+                );
+            }
+            other => panic!("unexpected translation {:?}", other),
+        }
+        assert_eq!(t.mapper.translate_page(page).unwrap(), frame);
+    }
+
+    #[test]
+    fn new_tables_are_zeroed() {
+        let mut tables = tables::<4>();
+        let flags = PageTableFlags::PRESENT | PageTableFlags::WRITABLE;
+        // Simulate an allocator that returns frames with stale content.
+        for table in &mut tables[1..] {
+            for entry in table.iter_mut() {
+                entry.set_addr(PhysAddr::new(0xdead_b000), flags);
+            }
+        }
+        let mut t = TestMapper::new(&mut tables);
+        t.map::<Size4KiB>(0x1000, 0x5000, flags);
+
+        assert_eq!(
+            t.mapper.translate_addr(VirtAddr::new(0x1234)),
+            Some(PhysAddr::new(0x5234))
+        );
+        assert!(!t.mapper.is_mapped(VirtAddr::new(0x2000)));
+        assert!(!t.mapper.is_mapped(VirtAddr::new(0x20_0000)));
+        assert!(!t.mapper.is_mapped(VirtAddr::new(0x4000_0000)));
+        for table in &tables[1..] {
+            assert_eq!(table.iter().filter(|entry| !entry.is_unused()).count(), 1);
+        }
+    }
+}
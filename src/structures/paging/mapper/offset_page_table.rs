@@ -92,6 +92,16 @@ impl Mapper<Size1GiB> for OffsetPageTable<'_> {
         self.inner.unmap(page)
     }
 
+    #[inline]
+    unsafe fn remap(
+        &mut self,
+        page: Page<Size1GiB>,
+        frame: PhysFrame<Size1GiB>,
+        flags: PageTableFlags,
+    ) -> Result<(PhysFrame<Size1GiB>, MapperFlush<Size1GiB>), UnmapError> {
+        unsafe { self.inner.remap(page, frame, flags) }
+    }
+
     #[inline]
     unsafe fn update_flags(
         &mut self,
@@ -161,6 +171,16 @@ impl Mapper<Size2MiB> for OffsetPageTable<'_> {
         self.inner.unmap(page)
     }
 
+    #[inline]
+    unsafe fn remap(
+        &mut self,
+        page: Page<Size2MiB>,
+        frame: PhysFrame<Size2MiB>,
+        flags: PageTableFlags,
+    ) -> Result<(PhysFrame<Size2MiB>, MapperFlush<Size2MiB>), UnmapError> {
+        unsafe { self.inner.remap(page, frame, flags) }
+    }
+
     #[inline]
     unsafe fn update_flags(
         &mut self,
@@ -230,6 +250,16 @@ impl Mapper<Size4KiB> for OffsetPageTable<'_> {
         self.inner.unmap(page)
     }
 
+    #[inline]
+    unsafe fn remap(
+        &mut self,
+        page: Page<Size4KiB>,
+        frame: PhysFrame<Size4KiB>,
+        flags: PageTableFlags,
+    ) -> Result<(PhysFrame<Size4KiB>, MapperFlush<Size4KiB>), UnmapError> {
+        unsafe { self.inner.remap(page, frame, flags) }
+    }
+
     #[inline]
     unsafe fn update_flags(
         &mut self,
@@ -277,6 +307,16 @@ impl Translate for OffsetPageTable<'_> {
     fn translate(&self, addr: VirtAddr) -> TranslateResult {
         self.inner.translate(addr)
     }
+
+    #[inline]
+    fn effective_flags(&self, addr: VirtAddr) -> Option<PageTableFlags> {
+        self.inner.effective_flags(addr)
+    }
+
+    #[inline]
+    fn unmapped_size(&self, addr: VirtAddr) -> u64 {
+        self.inner.unmapped_size(addr)
+    }
 }
 
 impl CleanUp for OffsetPageTable<'_> {
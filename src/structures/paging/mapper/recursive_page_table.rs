@@ -99,6 +99,109 @@ impl<'a> RecursivePageTable<'a> {
         self.p4
     }
 
+    /// Creates a new mapping in the page table, replacing an existing mapping of the page.
+    ///
+    /// This works like [`Mapper::map_to`], but instead of failing with
+    /// [`MapToError::PageAlreadyMapped`] if the page is already mapped, the existing mapping is
+    /// overwritten and the previously mapped frame is returned. If the page was not mapped
+    /// before, `None` is returned. This includes entries that are not present but contain other
+    /// data, e.g. swap information, which is overwritten as well.
+    ///
+    /// The old mapping might still be cached in the TLB, so the returned [`MapperFlush`] must be
+    /// flushed before the page is accessed.
+    ///
+    /// Returns [`MapToError::PageAlreadyMapped`] if `S` is a huge page size and the entry for the
+    /// page points to a lower level page table instead of a huge page. Returns
+    /// [`MapToError::InvalidFrameAddress`] if the existing entry points to an address that is
+    /// not aligned to the page size.
+    ///
+    /// ## Safety
+    ///
+    /// All safety requirements of [`Mapper::map_to`] apply. In addition, the caller must ensure
+    /// that no references into the page that were created for the old mapping are used after
+    /// the page has been remapped.
+    #[allow(clippy::type_complexity)]
+    pub unsafe fn map_to_remap<S, A>(
+        &mut self,
+        page: Page<S>,
+        frame: PhysFrame<S>,
+        flags: PageTableFlags,
+        allocator: &mut A,
+    ) -> Result<(Option<PhysFrame<S>>, MapperFlush<S>), MapToError<S>>
+    where
+        S: PageSize,
+        A: FrameAllocator<Size4KiB> + ?Sized,
+    {
+        use crate::structures::paging::PageTableFlags as Flags;
+
+        let parent_table_flags =
+            flags & (Flags::PRESENT | Flags::WRITABLE | Flags::USER_ACCESSIBLE);
+        let recursive_index = self.recursive_index;
+        // `p2_page`/`p1_page` require a page size that is not giant, so we use the 4KiB page at
+        // the same address for looking up the lower level tables.
+        let page_4kib = Page::<Size4KiB>::containing_address(page.start_address());
+
+        let p3 = unsafe {
+            Self::create_next_table(
+                &mut self.p4[page.p4_index()],
+                p3_page(page, recursive_index),
+                parent_table_flags,
+                allocator,
+            )?
+        };
+        let entry = if S::SIZE == Size1GiB::SIZE {
+            &mut p3[page.p3_index()]
+        } else {
+            let p2 = unsafe {
+                Self::create_next_table(
+                    &mut p3[page.p3_index()],
+                    p2_page(page_4kib, recursive_index),
+                    parent_table_flags,
+                    allocator,
+                )?
+            };
+            if S::SIZE == Size2MiB::SIZE {
+                &mut p2[page_4kib.p2_index()]
+            } else {
+                let p1 = unsafe {
+                    Self::create_next_table(
+                        &mut p2[page_4kib.p2_index()],
+                        p1_page(page_4kib, recursive_index),
+                        parent_table_flags,
+                        allocator,
+                    )?
+                };
+                &mut p1[page_4kib.p1_index()]
+            }
+        };
+
+        let is_huge = S::SIZE != Size4KiB::SIZE;
+        let old_frame = if !entry.flags().contains(Flags::PRESENT) {
+            None
+        } else if is_huge && !entry.flags().contains(Flags::HUGE_PAGE) {
+            return Err(MapToError::PageAlreadyMapped(frame));
+        } else {
+            // Bit 7 is the PAT bit in level 1 entries and bit 12 in huge page entries.
+            let addr = if is_huge {
+                entry.addr()
+            } else {
+                entry.level_1_addr()
+            };
+            let old_frame = PhysFrame::from_start_address(addr)
+                .map_err(|AddressNotAligned| MapToError::InvalidFrameAddress(addr))?;
+            Some(old_frame)
+        };
+
+        let flags = if is_huge {
+            flags | Flags::HUGE_PAGE
+        } else {
+            flags
+        };
+        entry.set_addr(frame.start_address(), flags);
+
+        Ok((old_frame, MapperFlush::new(page)))
+    }
+
     /// Internal helper function to create the page table of the next level if needed.
     ///
     /// If the passed entry is unused, a new frame is allocated from the given allocator, zeroed,
@@ -157,10 +260,13 @@ impl<'a> RecursivePageTable<'a> {
             }
 
             let page_table_ptr = next_table_page.start_address().as_mut_ptr();
-            let page_table: &mut PageTable = unsafe { &mut *(page_table_ptr) };
             if created {
-                page_table.zero();
+                // The table is only reachable through the recursive mapping after the entry
+                // was set, so it can't be cleared before that. Clear it before creating a
+                // reference to its stale content.
+                unsafe { PageTable::init_at(page_table_ptr) };
             }
+            let page_table: &mut PageTable = unsafe { &mut *(page_table_ptr) };
             Ok(page_table)
         }
 
@@ -293,7 +399,7 @@ impl<'a> RecursivePageTable<'a> {
         if !p1[page.p1_index()].is_unused() {
             return Err(MapToError::PageAlreadyMapped(frame));
         }
-        p1[page.p1_index()].set_frame(frame, flags);
+        p1[page.p1_index()].set_level_1_frame(frame, flags);
 
         Ok(MapperFlush::new(page))
     }
@@ -312,6 +418,7 @@ impl Mapper<Size1GiB> for RecursivePageTable<'_> {
     where
         A: FrameAllocator<Size4KiB> + ?Sized,
     {
+        debug_assert_user_parent_flags(flags, parent_table_flags);
         self.map_to_1gib(page, frame, flags, parent_table_flags, allocator)
     }
 
@@ -345,6 +452,38 @@ impl Mapper<Size1GiB> for RecursivePageTable<'_> {
         Ok((frame, MapperFlush::new(page)))
     }
 
+    unsafe fn remap(
+        &mut self,
+        page: Page<Size1GiB>,
+        frame: PhysFrame<Size1GiB>,
+        flags: PageTableFlags,
+    ) -> Result<(PhysFrame<Size1GiB>, MapperFlush<Size1GiB>), UnmapError> {
+        let p4 = &mut self.p4;
+        let p4_entry = &p4[page.p4_index()];
+
+        p4_entry.frame().map_err(|err| match err {
+            FrameError::FrameNotPresent => UnmapError::PageNotMapped,
+            FrameError::HugeFrame => UnmapError::ParentEntryHugePage,
+        })?;
+
+        let p3 = unsafe { &mut *(p3_ptr(page, self.recursive_index)) };
+        let p3_entry = &mut p3[page.p3_index()];
+        let entry_flags = p3_entry.flags();
+
+        if !entry_flags.contains(PageTableFlags::PRESENT) {
+            return Err(UnmapError::PageNotMapped);
+        }
+        if !entry_flags.contains(PageTableFlags::HUGE_PAGE) {
+            return Err(UnmapError::ParentEntryHugePage);
+        }
+
+        let old_frame = PhysFrame::from_start_address(p3_entry.addr())
+            .map_err(|AddressNotAligned| UnmapError::InvalidFrameAddress(p3_entry.addr()))?;
+
+        p3_entry.set_addr(frame.start_address(), flags | PageTableFlags::HUGE_PAGE);
+        Ok((old_frame, MapperFlush::new(page)))
+    }
+
     unsafe fn update_flags(
         &mut self,
         page: Page<Size1GiB>,
@@ -432,6 +571,7 @@ impl Mapper<Size2MiB> for RecursivePageTable<'_> {
     where
         A: FrameAllocator<Size4KiB> + ?Sized,
     {
+        debug_assert_user_parent_flags(flags, parent_table_flags);
         self.map_to_2mib(page, frame, flags, parent_table_flags, allocator)
     }
 
@@ -471,6 +611,44 @@ impl Mapper<Size2MiB> for RecursivePageTable<'_> {
         Ok((frame, MapperFlush::new(page)))
     }
 
+    unsafe fn remap(
+        &mut self,
+        page: Page<Size2MiB>,
+        frame: PhysFrame<Size2MiB>,
+        flags: PageTableFlags,
+    ) -> Result<(PhysFrame<Size2MiB>, MapperFlush<Size2MiB>), UnmapError> {
+        let p4 = &mut self.p4;
+        let p4_entry = &p4[page.p4_index()];
+        p4_entry.frame().map_err(|err| match err {
+            FrameError::FrameNotPresent => UnmapError::PageNotMapped,
+            FrameError::HugeFrame => UnmapError::ParentEntryHugePage,
+        })?;
+
+        let p3 = unsafe { &mut *(p3_ptr(page, self.recursive_index)) };
+        let p3_entry = &p3[page.p3_index()];
+        p3_entry.frame().map_err(|err| match err {
+            FrameError::FrameNotPresent => UnmapError::PageNotMapped,
+            FrameError::HugeFrame => UnmapError::ParentEntryHugePage,
+        })?;
+
+        let p2 = unsafe { &mut *(p2_ptr(page, self.recursive_index)) };
+        let p2_entry = &mut p2[page.p2_index()];
+        let entry_flags = p2_entry.flags();
+
+        if !entry_flags.contains(PageTableFlags::PRESENT) {
+            return Err(UnmapError::PageNotMapped);
+        }
+        if !entry_flags.contains(PageTableFlags::HUGE_PAGE) {
+            return Err(UnmapError::ParentEntryHugePage);
+        }
+
+        let old_frame = PhysFrame::from_start_address(p2_entry.addr())
+            .map_err(|AddressNotAligned| UnmapError::InvalidFrameAddress(p2_entry.addr()))?;
+
+        p2_entry.set_addr(frame.start_address(), flags | PageTableFlags::HUGE_PAGE);
+        Ok((old_frame, MapperFlush::new(page)))
+    }
+
     unsafe fn update_flags(
         &mut self,
         page: Page<Size2MiB>,
@@ -587,6 +765,7 @@ impl Mapper<Size4KiB> for RecursivePageTable<'_> {
     where
         A: FrameAllocator<Size4KiB> + ?Sized,
     {
+        debug_assert_user_parent_flags(flags, parent_table_flags);
         self.map_to_4kib(page, frame, flags, parent_table_flags, allocator)
     }
 
@@ -618,13 +797,50 @@ impl Mapper<Size4KiB> for RecursivePageTable<'_> {
         let p1 = unsafe { &mut *(p1_ptr(page, self.recursive_index)) };
         let p1_entry = &mut p1[page.p1_index()];
 
-        let frame = p1_entry.frame().map_err(|err| match err {
+        let frame = p1_entry
+            .level_1_frame()
+            .map_err(|_| UnmapError::PageNotMapped)?;
+
+        p1_entry.set_unused();
+        Ok((frame, MapperFlush::new(page)))
+    }
+
+    unsafe fn remap(
+        &mut self,
+        page: Page<Size4KiB>,
+        frame: PhysFrame<Size4KiB>,
+        flags: PageTableFlags,
+    ) -> Result<(PhysFrame<Size4KiB>, MapperFlush<Size4KiB>), UnmapError> {
+        let p4 = &mut self.p4;
+        let p4_entry = &p4[page.p4_index()];
+        p4_entry.frame().map_err(|err| match err {
             FrameError::FrameNotPresent => UnmapError::PageNotMapped,
             FrameError::HugeFrame => UnmapError::ParentEntryHugePage,
         })?;
 
-        p1_entry.set_unused();
-        Ok((frame, MapperFlush::new(page)))
+        let p3 = unsafe { &mut *(p3_ptr(page, self.recursive_index)) };
+        let p3_entry = &p3[page.p3_index()];
+        p3_entry.frame().map_err(|err| match err {
+            FrameError::FrameNotPresent => UnmapError::PageNotMapped,
+            FrameError::HugeFrame => UnmapError::ParentEntryHugePage,
+        })?;
+
+        let p2 = unsafe { &mut *(p2_ptr(page, self.recursive_index)) };
+        let p2_entry = &p2[page.p2_index()];
+        p2_entry.frame().map_err(|err| match err {
+            FrameError::FrameNotPresent => UnmapError::PageNotMapped,
+            FrameError::HugeFrame => UnmapError::ParentEntryHugePage,
+        })?;
+
+        let p1 = unsafe { &mut *(p1_ptr(page, self.recursive_index)) };
+        let p1_entry = &mut p1[page.p1_index()];
+
+        let old_frame = p1_entry
+            .level_1_frame()
+            .map_err(|_| UnmapError::PageNotMapped)?;
+
+        p1_entry.set_level_1_frame(frame, flags);
+        Ok((old_frame, MapperFlush::new(page)))
     }
 
     unsafe fn update_flags(
@@ -656,7 +872,7 @@ impl Mapper<Size4KiB> for RecursivePageTable<'_> {
             return Err(FlagUpdateError::PageNotMapped);
         }
 
-        p1[page.p1_index()].set_flags(flags);
+        p1[page.p1_index()].set_level_1_flags(flags);
 
         Ok(MapperFlush::new(page))
     }
@@ -758,8 +974,9 @@ impl Mapper<Size4KiB> for RecursivePageTable<'_> {
             return Err(TranslateError::PageNotMapped);
         }
 
-        PhysFrame::from_start_address(p1_entry.addr())
-            .map_err(|AddressNotAligned| TranslateError::InvalidFrameAddress(p1_entry.addr()))
+        PhysFrame::from_start_address(p1_entry.level_1_addr()).map_err(|AddressNotAligned| {
+            TranslateError::InvalidFrameAddress(p1_entry.level_1_addr())
+        })
     }
 }
 
@@ -818,24 +1035,99 @@ impl Translate for RecursivePageTable<'_> {
         if p1_entry.is_unused() {
             return TranslateResult::NotMapped;
         }
-        if p1_entry.flags().contains(PageTableFlags::HUGE_PAGE) {
-            panic!("level 1 entry has huge page bit set")
-        }
-
-        let frame = match PhysFrame::from_start_address(p1_entry.addr()) {
+        let frame = match PhysFrame::from_start_address(p1_entry.level_1_addr()) {
             Ok(frame) => frame,
-            Err(AddressNotAligned) => return TranslateResult::InvalidFrameAddress(p1_entry.addr()),
+            Err(AddressNotAligned) => {
+                return TranslateResult::InvalidFrameAddress(p1_entry.level_1_addr())
+            }
         };
         let offset = u64::from(addr.page_offset());
-        let flags = p1_entry.flags();
+        let flags = p1_entry.level_1_flags();
         TranslateResult::Mapped {
             frame: MappedFrame::Size4KiB(frame),
             offset,
             flags,
         }
     }
+
+    fn unmapped_size(&self, addr: VirtAddr) -> u64 {
+        let page = Page::containing_address(addr);
+
+        let p4_entry = &self.p4[addr.p4_index()];
+        if !p4_entry.flags().contains(PageTableFlags::PRESENT) {
+            return PageTableLevel::Four.entry_address_space_alignment();
+        }
+        if p4_entry.flags().contains(PageTableFlags::HUGE_PAGE) {
+            return 0;
+        }
+
+        let p3 = unsafe { &*(p3_ptr(page, self.recursive_index)) };
+        let p3_flags = p3[addr.p3_index()].flags();
+        if !p3_flags.contains(PageTableFlags::PRESENT) {
+            return PageTableLevel::Three.entry_address_space_alignment();
+        }
+        if p3_flags.contains(PageTableFlags::HUGE_PAGE) {
+            return 0;
+        }
+
+        let p2 = unsafe { &*(p2_ptr(page, self.recursive_index)) };
+        let p2_flags = p2[addr.p2_index()].flags();
+        if !p2_flags.contains(PageTableFlags::PRESENT) {
+            return PageTableLevel::Two.entry_address_space_alignment();
+        }
+        if p2_flags.contains(PageTableFlags::HUGE_PAGE) {
+            return 0;
+        }
+
+        let p1 = unsafe { &*(p1_ptr(page, self.recursive_index)) };
+        if p1[addr.p1_index()].is_unused() {
+            Size4KiB::SIZE
+        } else {
+            0
+        }
+    }
+
+    fn effective_flags(&self, addr: VirtAddr) -> Option<PageTableFlags> {
+        let page = Page::containing_address(addr);
+
+        let p4_entry = &self.p4[addr.p4_index()];
+        let flags = p4_entry.flags();
+        if !flags.contains(PageTableFlags::PRESENT) || flags.contains(PageTableFlags::HUGE_PAGE) {
+            return None;
+        }
+
+        let p3 = unsafe { &*(p3_ptr(page, self.recursive_index)) };
+        let p3_entry = &p3[addr.p3_index()];
+        if !p3_entry.flags().contains(PageTableFlags::PRESENT) {
+            return None;
+        }
+        let flags = restrict_flags(p3_entry.flags(), flags);
+        if p3_entry.flags().contains(PageTableFlags::HUGE_PAGE) {
+            return Some(flags);
+        }
+
+        let p2 = unsafe { &*(p2_ptr(page, self.recursive_index)) };
+        let p2_entry = &p2[addr.p2_index()];
+        if !p2_entry.flags().contains(PageTableFlags::PRESENT) {
+            return None;
+        }
+        let flags = restrict_flags(p2_entry.flags(), flags);
+        if p2_entry.flags().contains(PageTableFlags::HUGE_PAGE) {
+            return Some(flags);
+        }
+
+        let p1 = unsafe { &*(p1_ptr(page, self.recursive_index)) };
+        let p1_entry = &p1[addr.p1_index()];
+        if p1_entry.is_unused() {
+            return None;
+        }
+        Some(restrict_flags(p1_entry.level_1_flags(), flags))
+    }
 }
 
+/// Empty page tables are accessed through their recursive addresses. After
+/// freeing a page table, the TLB entry for its recursive address is flushed, so
+/// that the page table entry can safely be reused for a new table.
 impl CleanUp for RecursivePageTable<'_> {
     #[inline]
     unsafe fn clean_up<D>(&mut self, frame_deallocator: &mut D)
@@ -901,9 +1193,9 @@ impl CleanUp for RecursivePageTable<'_> {
                         let start = start.max(range.start);
                         let end = Page::<Size4KiB>::containing_address(end);
                         let end = end.min(range.end);
-                        let page_table =
+                        let page_table_ptr =
                             [p1_ptr, p2_ptr, p3_ptr][level as usize - 2](start, recursive_index);
-                        let page_table = unsafe { &mut *page_table };
+                        let page_table = unsafe { &mut *page_table_ptr };
                         if clean_up(
                             recursive_index,
                             page_table,
@@ -912,6 +1204,10 @@ impl CleanUp for RecursivePageTable<'_> {
                             frame_deallocator,
                         ) {
                             entry.set_unused();
+                            // The freed table was accessed through its recursive address, so
+                            // the TLB might still cache a translation to the freed frame. It
+                            // must be flushed before the entry is reused for a new table.
+                            crate::instructions::tlb::flush(VirtAddr::new(page_table_ptr as u64));
                             unsafe {
                                 frame_deallocator.deallocate_frame(frame);
                             }
@@ -3,7 +3,7 @@
 //! Page tables translate virtual memory “pages” to physical memory “frames”.
 
 pub use self::frame::PhysFrame;
-pub use self::frame_alloc::{FrameAllocator, FrameDeallocator};
+pub use self::frame_alloc::{CountingFrameAllocator, FrameAllocator, FrameDeallocator};
 #[doc(no_inline)]
 pub use self::mapper::MappedPageTable;
 #[cfg(target_pointer_width = "64")]
@@ -14,10 +14,14 @@ pub use self::mapper::OffsetPageTable;
 pub use self::mapper::RecursivePageTable;
 pub use self::mapper::{Mapper, Translate};
 pub use self::page::{Page, PageSize, Size1GiB, Size2MiB, Size4KiB};
-pub use self::page_table::{PageOffset, PageTable, PageTableFlags, PageTableIndex};
+pub use self::page_table::{
+    CacheType, PageOffset, PageTable, PageTableFlags, PageTableIndex, PageTableIndices,
+};
 
 pub mod frame;
 mod frame_alloc;
 pub mod mapper;
 pub mod page;
 pub mod page_table;
+#[cfg(all(test, target_pointer_width = "64"))]
+mod test_util;
@@ -87,12 +87,16 @@ impl<S: PageSize> Page<S> {
 
     /// Returns the page that starts at the given virtual address.
     ///
+    /// Unlike [`from_start_address`](Self::from_start_address), this skips the alignment check,
+    /// which makes it useful in hot paths where the alignment is already known.
+    ///
     /// ## Safety
     ///
-    /// The address must be correctly aligned.
+    /// The address must be aligned to `S::SIZE`. This is only checked in debug builds.
     #[inline]
     #[rustversion::attr(since(1.61), const)]
     pub unsafe fn from_start_address_unchecked(start_address: VirtAddr) -> Self {
+        debug_assert!(start_address.is_aligned_u64(S::SIZE));
         Page {
             start_address,
             size: PhantomData,
@@ -123,6 +127,61 @@ impl<S: PageSize> Page<S> {
         S::SIZE
     }
 
+    /// Returns the page of size `T` that contains the start address of this page.
+    ///
+    /// If `T` is larger than `S`, this is the page of size `T` that contains this page. If `T`
+    /// is smaller than `S`, it is the first page of size `T` inside this page.
+    ///
+    /// This is useful for finding huge page boundaries, e.g.
+    /// `page.align_down_to::<Size2MiB>()` returns the 2MiB page containing a 4KiB page.
+    #[inline]
+    pub fn align_down_to<T: PageSize>(self) -> Page<T> {
+        Page::containing_address(self.start_address)
+    }
+
+    /// Returns the first page of size `T` that starts at or after the start of this page.
+    ///
+    /// See [`VirtAddr::align_up`] for details on the alignment.
+    ///
+    /// ## Panics
+    ///
+    /// This function panics if the resulting address is higher than `0xffff_ffff_ffff_ffff`.
+    #[inline]
+    pub fn align_up_to<T: PageSize>(self) -> Page<T> {
+        Page::containing_address(self.start_address.align_up(T::SIZE))
+    }
+
+    /// Returns the memory of this page as a byte slice.
+    ///
+    /// ## Safety
+    ///
+    /// The caller must ensure that the complete page is mapped and readable for the lifetime
+    /// `'a`, and that the memory is not mutated during that time, e.g. through a mutable
+    /// reference or by hardware.
+    #[cfg(target_pointer_width = "64")]
+    #[inline]
+    pub unsafe fn as_slice<'a>(self) -> &'a [u8] {
+        unsafe { core::slice::from_raw_parts(self.start_address.as_ptr(), S::SIZE as usize) }
+    }
+
+    /// Returns the memory of this page as a mutable byte slice.
+    ///
+    /// This can be used to zero or fill a freshly mapped page.
+    ///
+    /// ## Safety
+    ///
+    /// The caller must ensure that the complete page is mapped and writable for the lifetime
+    /// `'a`, and that the memory is not accessed through any other reference or pointer during
+    /// that time. In particular, this must not be called for the same page again while the
+    /// returned slice is alive.
+    #[cfg(target_pointer_width = "64")]
+    #[inline]
+    pub unsafe fn as_slice_mut<'a>(self) -> &'a mut [u8] {
+        unsafe {
+            core::slice::from_raw_parts_mut(self.start_address.as_mut_ptr(), S::SIZE as usize)
+        }
+    }
+
     /// Returns the level 4 page table index of this page.
     #[inline]
     #[rustversion::attr(since(1.61), const)]
@@ -158,6 +217,30 @@ impl<S: PageSize> Page<S> {
         PageRangeInclusive { start, end }
     }
 
+    /// Returns the page `count` pages after this page.
+    ///
+    /// Returns `None` if the start address of the resulting page would not be
+    /// canonical. Like the `+` operator, this does not jump the gap between
+    /// the lower and the higher half of the address space.
+    #[inline]
+    pub fn checked_add(self, count: u64) -> Option<Self> {
+        let offset = count.checked_mul(S::SIZE)?;
+        let addr = self.start_address.as_u64().checked_add(offset)?;
+        Some(Page::containing_address(VirtAddr::try_new(addr).ok()?))
+    }
+
+    /// Returns the page `count` pages before this page.
+    ///
+    /// Returns `None` if the start address of the resulting page would be
+    /// negative or not canonical. Like the `-` operator, this does not jump
+    /// the gap between the lower and the higher half of the address space.
+    #[inline]
+    pub fn checked_sub(self, count: u64) -> Option<Self> {
+        let offset = count.checked_mul(S::SIZE)?;
+        let addr = self.start_address.as_u64().checked_sub(offset)?;
+        Some(Page::containing_address(VirtAddr::try_new(addr).ok()?))
+    }
+
     // FIXME: Move this into the `Step` impl, once `Step` is stabilized.
     #[cfg(any(feature = "instructions", feature = "step_trait"))]
     pub(crate) fn steps_between_impl(start: &Self, end: &Self) -> (usize, Option<usize>) {
@@ -356,6 +439,24 @@ impl<S: PageSize> PageRange<S> {
     pub fn size(&self) -> u64 {
         S::SIZE * self.len()
     }
+
+    /// Splits the range into the pages before `page` and the pages starting at `page`.
+    ///
+    /// If `page` lies outside of the range, one of the two halves is empty.
+    #[inline]
+    pub fn split_at(&self, page: Page<S>) -> (PageRange<S>, PageRange<S>) {
+        let mid = page.min(self.end).max(self.start);
+        (
+            PageRange {
+                start: self.start,
+                end: mid,
+            },
+            PageRange {
+                start: mid,
+                end: self.end,
+            },
+        )
+    }
 }
 
 impl<S: PageSize> Iterator for PageRange<S> {
@@ -483,6 +584,98 @@ mod tests {
         test_is_hash::<Page<Size1GiB>>();
     }
 
+    #[test]
+    pub fn test_page_debug() {
+        let page = Page::<Size4KiB>::containing_address(VirtAddr::new(0x1000));
+        assert_eq!(format!("{:?}", page), "Page[4KiB](0x1000)");
+        let page = Page::<Size2MiB>::containing_address(VirtAddr::new(0x20_0000));
+        assert_eq!(format!("{:?}", page), "Page[2MiB](0x200000)");
+        let page = Page::<Size1GiB>::containing_address(VirtAddr::new(0x4000_0000));
+        assert_eq!(format!("{:?}", page), "Page[1GiB](0x40000000)");
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    pub fn test_page_as_slice() {
+        #[repr(C, align(4096))]
+        struct AlignedPage([u8; 4096]);
+
+        let mut memory = AlignedPage([0xAB; 4096]);
+        let page: Page =
+            Page::from_start_address(VirtAddr::from_ptr(core::ptr::addr_of_mut!(memory))).unwrap();
+
+        let slice = unsafe { page.as_slice_mut() };
+        assert_eq!(slice.len(), 4096);
+        slice.fill(0);
+        slice[42] = 1;
+
+        let slice = unsafe { page.as_slice() };
+        assert_eq!(slice.iter().filter(|&&b| b != 0).count(), 1);
+        assert_eq!(memory.0[42], 1);
+    }
+
+    #[test]
+    pub fn test_page_align_to() {
+        let page: Page = Page::containing_address(VirtAddr::new(0x20_0000));
+        assert_eq!(
+            page.align_down_to::<Size2MiB>().start_address(),
+            VirtAddr::new(0x20_0000)
+        );
+        assert_eq!(
+            page.align_up_to::<Size2MiB>().start_address(),
+            VirtAddr::new(0x20_0000)
+        );
+
+        let page = page + 1;
+        assert_eq!(
+            page.align_down_to::<Size2MiB>().start_address(),
+            VirtAddr::new(0x20_0000)
+        );
+        assert_eq!(
+            page.align_up_to::<Size2MiB>().start_address(),
+            VirtAddr::new(0x40_0000)
+        );
+        assert_eq!(
+            page.align_down_to::<Size1GiB>().start_address(),
+            VirtAddr::new(0)
+        );
+        assert_eq!(
+            page.align_up_to::<Size1GiB>().start_address(),
+            VirtAddr::new(0x4000_0000)
+        );
+
+        let page: Page = Page::containing_address(VirtAddr::new(0xffff_ffff_ffff_f000));
+        assert_eq!(
+            page.align_down_to::<Size2MiB>().start_address(),
+            VirtAddr::new(0xffff_ffff_ffe0_0000)
+        );
+    }
+
+    #[test]
+    pub fn test_page_range_split_at() {
+        let start: Page = Page::containing_address(VirtAddr::new(0x10_0000));
+        let end = start + 10;
+        let range = Page::range(start, end);
+
+        let (low, high) = range.split_at(start + 4);
+        assert_eq!(low, Page::range(start, start + 4));
+        assert_eq!(high, Page::range(start + 4, end));
+        assert_eq!(low.len() + high.len(), range.len());
+
+        let (low, high) = range.split_at(start - 1);
+        assert!(low.is_empty());
+        assert_eq!(high, range);
+
+        let (low, high) = range.split_at(end + 1);
+        assert_eq!(low, range);
+        assert!(high.is_empty());
+
+        let empty = Page::range(end, start);
+        let (low, high) = empty.split_at(start + 4);
+        assert!(low.is_empty());
+        assert!(high.is_empty());
+    }
+
     #[test]
     pub fn test_page_ranges() {
         let page_size = Size4KiB::SIZE;
@@ -511,6 +704,27 @@ mod tests {
         assert_eq!(range_inclusive.next(), None);
     }
 
+    #[test]
+    pub fn test_page_checked_arithmetic() {
+        let last: Page = Page::containing_address(VirtAddr::new(u64::MAX));
+        assert_eq!(last.checked_add(1), None);
+        assert_eq!(last.checked_add(u64::MAX), None);
+        assert_eq!((last - 1).checked_add(1), Some(last));
+        assert_eq!(last.checked_sub(1), Some(last - 1));
+
+        let first: Page = Page::containing_address(VirtAddr::new(0));
+        assert_eq!(first.checked_sub(1), None);
+        assert_eq!(first.checked_add(2), Some(first + 2));
+
+        // the gap between the lower and the higher half is not jumped
+        let lower_half_end: Page<Size2MiB> =
+            Page::containing_address(VirtAddr::new(0x7fff_ffff_ffff));
+        assert_eq!(lower_half_end.checked_add(1), None);
+        let higher_half_start: Page<Size2MiB> =
+            Page::containing_address(VirtAddr::new(0xffff_8000_0000_0000));
+        assert_eq!(higher_half_start.checked_sub(1), None);
+    }
+
     #[test]
     pub fn test_page_range_inclusive_overflow() {
         let page_size = Size4KiB::SIZE;
@@ -648,4 +862,20 @@ mod tests {
             assert_eq!(Step::steps_between(&start, &end), (lower, upper));
         }
     }
+
+    #[test]
+    #[cfg(feature = "step_trait")]
+    fn page_step_inclusive_range() {
+        let last: Page = Page::containing_address(VirtAddr::new(u64::MAX));
+        let pages: Vec<_> = (last - 2..=last).collect();
+        assert_eq!(pages, [last - 2, last - 1, last]);
+        assert_eq!((last..=last).count(), 1);
+
+        // the gap between the lower and the higher half is jumped
+        let lower_half_end: Page = Page::containing_address(VirtAddr::new(0x7fff_ffff_ffff));
+        let higher_half_start: Page =
+            Page::containing_address(VirtAddr::new(0xffff_8000_0000_0000));
+        let pages: Vec<_> = (lower_half_end..=higher_half_start).collect();
+        assert_eq!(pages, [lower_half_end, higher_half_start]);
+    }
 }
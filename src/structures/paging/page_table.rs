@@ -5,8 +5,8 @@ use core::fmt;
 use core::iter::Step;
 use core::ops::{Index, IndexMut};
 
-use super::{PageSize, PhysFrame, Size4KiB};
-use crate::addr::PhysAddr;
+use super::{PageSize, PhysFrame, Size1GiB, Size2MiB, Size4KiB};
+use crate::addr::{PhysAddr, VirtAddr};
 
 use bitflags::bitflags;
 
@@ -20,6 +20,9 @@ pub enum FrameError {
     HugeFrame,
 }
 
+/// The bits of a page table entry that contain the physical address.
+const ADDRESS_MASK: u64 = 0x000f_ffff_ffff_f000;
+
 /// A 64-bit page table entry.
 #[derive(Clone)]
 #[repr(transparent)]
@@ -47,15 +50,30 @@ impl PageTableEntry {
     }
 
     /// Returns the flags of this entry.
+    ///
+    /// Bit 12 is only returned as [`PageTableFlags::HUGE_PAGE_PAT`] if the `HUGE_PAGE` flag is
+    /// set, otherwise it is part of the address. Since bit 7 of level 1 entries is the PAT bit
+    /// instead of the `HUGE_PAGE` flag, use [`level_1_flags`](Self::level_1_flags) for them.
     #[inline]
     pub const fn flags(&self) -> PageTableFlags {
-        PageTableFlags::from_bits_truncate(self.entry)
+        if self.entry & PageTableFlags::HUGE_PAGE.bits() != 0 {
+            PageTableFlags::from_bits_truncate(self.entry)
+        } else {
+            self.level_1_flags()
+        }
     }
 
     /// Returns the physical address mapped by this entry, might be zero.
+    ///
+    /// If the `HUGE_PAGE` flag is set, bit 12 is the PAT bit and not part of the address. Use
+    /// [`level_1_addr`](Self::level_1_addr) for level 1 entries.
     #[inline]
     pub fn addr(&self) -> PhysAddr {
-        PhysAddr::new(self.entry & 0x000f_ffff_ffff_f000)
+        if self.flags().contains(PageTableFlags::HUGE_PAGE) {
+            PhysAddr::new(self.entry & ADDRESS_MASK & !PageTableFlags::HUGE_PAGE_PAT.bits())
+        } else {
+            self.level_1_addr()
+        }
     }
 
     /// Returns the physical frame mapped by this entry.
@@ -90,11 +108,156 @@ impl PageTableEntry {
         self.set_addr(frame.start_address(), flags)
     }
 
+    /// Returns the flags of this entry, which must be an entry of a level 1 table.
+    ///
+    /// In level 1 entries, bit 7 is the PAT bit, which is returned as
+    /// [`PageTableFlags::HUGE_PAGE`], and bit 12 is always part of the address.
+    #[inline]
+    pub const fn level_1_flags(&self) -> PageTableFlags {
+        PageTableFlags::from_bits_truncate(self.entry & !PageTableFlags::HUGE_PAGE_PAT.bits())
+    }
+
+    /// Returns the physical address mapped by this entry, which must be an entry of a level 1
+    /// table.
+    #[inline]
+    pub fn level_1_addr(&self) -> PhysAddr {
+        PhysAddr::new(self.entry & ADDRESS_MASK)
+    }
+
+    /// Returns the physical frame mapped by this entry, which must be an entry of a level 1
+    /// table.
+    ///
+    /// Unlike [`frame`](Self::frame), this treats bit 7 as the PAT bit, so the only error is
+    /// `FrameError::FrameNotPresent` if the entry doesn't have the `PRESENT` flag set.
+    #[inline]
+    pub fn level_1_frame(&self) -> Result<PhysFrame, FrameError> {
+        if !self.level_1_flags().contains(PageTableFlags::PRESENT) {
+            Err(FrameError::FrameNotPresent)
+        } else {
+            Ok(PhysFrame::containing_address(self.level_1_addr()))
+        }
+    }
+
+    /// Map the entry, which must be an entry of a level 1 table, to the specified physical frame
+    /// with the specified flags.
+    ///
+    /// Unlike [`set_frame`](Self::set_frame), `flags` may contain [`PageTableFlags::HUGE_PAGE`],
+    /// which sets the PAT bit of the entry.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `flags` contains [`PageTableFlags::HUGE_PAGE_PAT`], which overlaps with the
+    /// address in level 1 entries.
+    #[inline]
+    pub fn set_level_1_frame(&mut self, frame: PhysFrame, flags: PageTableFlags) {
+        assert!(!flags.contains(PageTableFlags::HUGE_PAGE_PAT));
+        self.set_addr(frame.start_address(), flags)
+    }
+
+    /// Sets the flags of this entry, which must be an entry of a level 1 table.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `flags` contains [`PageTableFlags::HUGE_PAGE_PAT`], which overlaps with the
+    /// address in level 1 entries.
+    #[inline]
+    pub fn set_level_1_flags(&mut self, flags: PageTableFlags) {
+        assert!(!flags.contains(PageTableFlags::HUGE_PAGE_PAT));
+        self.entry = self.level_1_addr().as_u64() | flags.bits();
+    }
+
+    /// Map the entry to the specified 2MiB frame with the specified flags.
+    ///
+    /// This is only valid for entries of a level 2 table. The `HUGE_PAGE` flag is added to
+    /// `flags`. Since a `PhysFrame<Size2MiB>` is always 2MiB aligned, the entry can't be
+    /// misaligned.
+    #[inline]
+    pub fn set_huge_frame_2mib(&mut self, frame: PhysFrame<Size2MiB>, flags: PageTableFlags) {
+        self.set_addr(frame.start_address(), flags | PageTableFlags::HUGE_PAGE)
+    }
+
+    /// Map the entry to the specified 1GiB frame with the specified flags.
+    ///
+    /// This is only valid for entries of a level 3 table. The `HUGE_PAGE` flag is added to
+    /// `flags`. Since a `PhysFrame<Size1GiB>` is always 1GiB aligned, the entry can't be
+    /// misaligned.
+    #[inline]
+    pub fn set_huge_frame_1gib(&mut self, frame: PhysFrame<Size1GiB>, flags: PageTableFlags) {
+        self.set_addr(frame.start_address(), flags | PageTableFlags::HUGE_PAGE)
+    }
+
     /// Sets the flags of this entry.
     #[inline]
     pub fn set_flags(&mut self, flags: PageTableFlags) {
         self.entry = self.addr().as_u64() | flags.bits();
     }
+
+    /// Returns the bits of this entry that are available to the OS.
+    ///
+    /// The entry has 14 bits that are ignored by the CPU: bits 9..12 and bits 52..63. They are
+    /// returned packed together, with bits 9..12 of the entry in bits 0..3 of the result and
+    /// bits 52..63 of the entry in bits 3..14 of the result. The same bits are also accessible
+    /// through the `BIT_9` to `BIT_62` flags.
+    ///
+    /// Note that bits 59..63 are used as protection key if `Cr4Flags::PROTECTION_KEY_USER` or
+    /// `Cr4Flags::PROTECTION_KEY_SUPERVISOR` is set.
+    #[inline]
+    pub const fn available_bits(&self) -> u16 {
+        let low = (self.entry >> 9) & 0b111;
+        let high = (self.entry >> 52) & 0x7ff;
+        (low | (high << 3)) as u16
+    }
+
+    /// Sets the bits of this entry that are available to the OS, without changing the address
+    /// or any hardware flags.
+    ///
+    /// See [`available_bits`](Self::available_bits) for the layout of `bits`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `bits` has any bit at or above bit 14 set.
+    #[inline]
+    pub fn set_available_bits(&mut self, bits: u16) {
+        assert!(bits < (1 << 14), "only 14 bits are available");
+        let bits = u64::from(bits);
+        let mask = (0b111 << 9) | (0x7ff << 52);
+        self.entry = (self.entry & !mask) | ((bits & 0b111) << 9) | ((bits >> 3) << 52);
+    }
+
+    /// Clears the `ACCESSED` flag of this entry and returns whether it was set.
+    ///
+    /// The flag is cleared with a single atomic read-modify-write, so an update of the entry
+    /// by the CPU's page walker can't be lost. The TLB may still cache the old entry, so it
+    /// must be flushed before the flag is set again reliably.
+    #[inline]
+    pub fn take_accessed(&mut self) -> bool {
+        self.take_flag(PageTableFlags::ACCESSED)
+    }
+
+    /// Clears the `DIRTY` flag of this entry and returns whether it was set.
+    ///
+    /// The flag is cleared with a single atomic read-modify-write, so an update of the entry
+    /// by the CPU's page walker can't be lost. The TLB may still cache the old entry, so it
+    /// must be flushed before the flag is set again reliably.
+    #[inline]
+    pub fn take_dirty(&mut self) -> bool {
+        self.take_flag(PageTableFlags::DIRTY)
+    }
+
+    #[inline]
+    fn take_flag(&mut self, flag: PageTableFlags) -> bool {
+        #[cfg(target_arch = "x86_64")]
+        let old = {
+            use core::sync::atomic::{AtomicU64, Ordering};
+            // SAFETY: `AtomicU64` has the same layout as `u64` on x86_64, and we hold a unique
+            // reference to the entry.
+            let entry = unsafe { &*(&mut self.entry as *mut u64 as *const AtomicU64) };
+            entry.fetch_and(!flag.bits(), Ordering::SeqCst)
+        };
+        #[cfg(not(target_arch = "x86_64"))]
+        let old = core::mem::replace(&mut self.entry, self.entry & !flag.bits());
+        old & flag.bits() != 0
+    }
 }
 
 impl Default for PageTableEntry {
@@ -138,6 +301,9 @@ bitflags! {
         const DIRTY =           1 << 6;
         /// Specifies that the entry maps a huge frame instead of a page table. Only allowed in
         /// P2 or P3 tables.
+        ///
+        /// In P1 tables, this bit is the PAT bit instead, which selects one of the upper four
+        /// entries of the page attribute table. See [`CacheType`] for details.
         const HUGE_PAGE =       1 << 7;
         /// Indicates that the mapping is present in all address spaces, so it isn't flushed from
         /// the TLB on an address space switch.
@@ -148,6 +314,12 @@ bitflags! {
         const BIT_10 =          1 << 10;
         /// Available to the OS, can be used to store additional data, e.g. custom flags.
         const BIT_11 =          1 << 11;
+        /// The PAT bit of a huge page, which selects one of the upper four entries of the page
+        /// attribute table. See [`CacheType`] for details.
+        ///
+        /// Only valid together with the `HUGE_PAGE` flag. In all other entries, this bit is
+        /// part of the address.
+        const HUGE_PAGE_PAT =   1 << 12;
         /// Available to the OS, can be used to store additional data, e.g. custom flags.
         const BIT_52 =          1 << 52;
         /// Available to the OS, can be used to store additional data, e.g. custom flags.
@@ -178,6 +350,84 @@ bitflags! {
     }
 }
 
+/// The memory type of a mapping, selected through the page attribute table (PAT).
+///
+/// The `WRITE_THROUGH` and `NO_CACHE` flags and the PAT bit of a leaf page table entry form
+/// the index of the PAT entry that determines the memory type. The PAT bit is bit 7 of level 1
+/// entries, i.e. [`PageTableFlags::HUGE_PAGE`], and bit 12 of huge page entries, i.e.
+/// [`PageTableFlags::HUGE_PAGE_PAT`]. [`flags`](Self::flags) selects the right one for the
+/// given page size.
+///
+/// This type assumes the following PAT layout:
+///
+/// | PAT entry | PAT bit | `NO_CACHE` | `WRITE_THROUGH` | Memory type              |
+/// |-----------|---------|------------|-----------------|--------------------------|
+/// | 0, 4      | 0, 1    | 0          | 0               | Write-back (WB)          |
+/// | 1         | 0       | 0          | 1               | Write-through (WT)       |
+/// | 2, 6      | 0, 1    | 1          | 0               | Uncached minus (UC-)     |
+/// | 3, 7      | 0, 1    | 1          | 1               | Uncacheable (UC)         |
+/// | 5         | 1       | 0          | 1               | Write-combining (WC)     |
+///
+/// This is the power-on default of the PAT, except for entry 5, which is write-through by
+/// default. So mappings without the PAT bit keep their memory type, but
+/// [`PAT_MSR_VALUE`](Self::PAT_MSR_VALUE) has to be written to the `IA32_PAT` MSR before
+/// using [`WriteCombining`](Self::WriteCombining) mappings. The PAT must be the same on all
+/// CPUs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheType {
+    /// Write-back caching, the default for normal memory.
+    WriteBack,
+    /// Write-through caching.
+    WriteThrough,
+    /// Uncached, but can be overridden by write-combining MTRRs.
+    UncachedMinus,
+    /// Uncacheable, e.g. for memory-mapped device registers.
+    Uncacheable,
+    /// Write-combining, e.g. for framebuffers.
+    WriteCombining,
+}
+
+impl CacheType {
+    /// The value of the `IA32_PAT` MSR for the PAT layout that this type assumes.
+    pub const PAT_MSR_VALUE: u64 = 0x0007_0106_0007_0406;
+
+    /// Returns the index of the PAT entry for this memory type.
+    #[inline]
+    pub const fn pat_index(self) -> u8 {
+        match self {
+            CacheType::WriteBack => 0,
+            CacheType::WriteThrough => 1,
+            CacheType::UncachedMinus => 2,
+            CacheType::Uncacheable => 3,
+            CacheType::WriteCombining => 5,
+        }
+    }
+
+    /// Returns the page table flags that select this memory type for a page of size `S`.
+    ///
+    /// The PAT bit is returned as `HUGE_PAGE` for 4KiB pages and as `HUGE_PAGE_PAT` for huge
+    /// pages.
+    #[inline]
+    pub fn flags<S: PageSize>(self) -> PageTableFlags {
+        let index = self.pat_index();
+        let mut flags = PageTableFlags::empty();
+        if index & 0b001 != 0 {
+            flags |= PageTableFlags::WRITE_THROUGH;
+        }
+        if index & 0b010 != 0 {
+            flags |= PageTableFlags::NO_CACHE;
+        }
+        if index & 0b100 != 0 {
+            flags |= if S::SIZE == Size4KiB::SIZE {
+                PageTableFlags::HUGE_PAGE
+            } else {
+                PageTableFlags::HUGE_PAGE_PAT
+            };
+        }
+        flags
+    }
+}
+
 /// The number of entries in a page table.
 const ENTRY_COUNT: usize = 512;
 
@@ -207,6 +457,23 @@ impl PageTable {
         }
     }
 
+    /// Initializes the memory at `ptr` as an empty page table.
+    ///
+    /// This is equivalent to `ptr.write(PageTable::new())`, but writes the zeroes directly to
+    /// the target memory instead of constructing the table on the stack first. It is intended
+    /// for freshly allocated page table frames, whose content is uninitialized or contains
+    /// stale data. Unlike [`zero`](Self::zero), it doesn't require creating a reference to the
+    /// uninitialized table first.
+    ///
+    /// ## Safety
+    ///
+    /// The caller must ensure that `ptr` is valid for writes and properly aligned, e.g. that it
+    /// points to a mapped 4KiB frame that is not in use otherwise.
+    #[inline]
+    pub unsafe fn init_at(ptr: *mut PageTable) {
+        unsafe { ptr.write_bytes(0, 1) }
+    }
+
     /// Clears all entries.
     #[inline]
     pub fn zero(&mut self) {
@@ -421,6 +688,23 @@ impl From<PageOffset> for usize {
     }
 }
 
+/// The page table indices and page offset of a virtual address.
+///
+/// This type is returned by the `VirtAddr::table_indices` method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PageTableIndices {
+    /// The index into the level 4 page table.
+    pub p4: PageTableIndex,
+    /// The index into the level 3 page table.
+    pub p3: PageTableIndex,
+    /// The index into the level 2 page table.
+    pub p2: PageTableIndex,
+    /// The index into the level 1 page table.
+    pub p1: PageTableIndex,
+    /// The offset into the 4KiB page.
+    pub offset: PageOffset,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 /// A value between 1 and 4.
 pub enum PageTableLevel {
@@ -455,6 +739,23 @@ impl PageTableLevel {
         }
     }
 
+    /// Returns an iterator over all levels, starting at level 4 and ending at level 1.
+    ///
+    /// This is the order in which the page table hierarchy is walked when translating an
+    /// address.
+    #[inline]
+    pub fn top_down() -> impl Iterator<Item = PageTableLevel> {
+        core::iter::successors(Some(PageTableLevel::Four), |level| level.next_lower_level())
+    }
+
+    /// Returns the index into a table of this level for the given virtual address.
+    ///
+    /// This is equivalent to [`VirtAddr::page_table_index`].
+    #[inline]
+    pub const fn addr_index(self, addr: VirtAddr) -> PageTableIndex {
+        addr.page_table_index(self)
+    }
+
     /// Returns the alignment for the address space described by a table of this level.
     pub const fn table_address_space_alignment(self) -> u64 {
         1u64 << (self as u8 * 9 + 12)
@@ -465,3 +766,151 @@ impl PageTableLevel {
         1u64 << (((self as u8 - 1) * 9) + 12)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_huge_frames() {
+        let flags = PageTableFlags::PRESENT | PageTableFlags::WRITABLE;
+        let mut entry = PageTableEntry::new();
+
+        let frame = PhysFrame::<Size2MiB>::containing_address(PhysAddr::new(0x40_0000));
+        entry.set_huge_frame_2mib(frame, flags);
+        assert_eq!(entry.addr(), frame.start_address());
+        assert_eq!(entry.flags(), flags | PageTableFlags::HUGE_PAGE);
+        assert_eq!(entry.frame(), Err(FrameError::HugeFrame));
+
+        let frame = PhysFrame::<Size1GiB>::containing_address(PhysAddr::new(0x8000_0000));
+        entry.set_huge_frame_1gib(frame, flags | PageTableFlags::HUGE_PAGE);
+        assert_eq!(entry.addr(), frame.start_address());
+        assert_eq!(entry.flags(), flags | PageTableFlags::HUGE_PAGE);
+    }
+
+    #[test]
+    fn available_bits() {
+        let mut entry = PageTableEntry::new();
+        let flags = PageTableFlags::PRESENT | PageTableFlags::WRITABLE | PageTableFlags::NO_EXECUTE;
+        entry.set_addr(PhysAddr::new(0x000f_ffff_ffff_f000), flags);
+        assert_eq!(entry.available_bits(), 0);
+
+        entry.set_available_bits(0x3fff);
+        assert_eq!(entry.available_bits(), 0x3fff);
+        assert_eq!(entry.addr(), PhysAddr::new(0x000f_ffff_ffff_f000));
+        assert!(entry
+            .flags()
+            .contains(flags | PageTableFlags::BIT_9 | PageTableFlags::BIT_62));
+
+        entry.set_available_bits(0b1001);
+        assert_eq!(entry.available_bits(), 0b1001);
+        assert_eq!(
+            entry.flags(),
+            flags | PageTableFlags::BIT_9 | PageTableFlags::BIT_52
+        );
+    }
+
+    #[test]
+    fn page_table_levels() {
+        let mut levels = PageTableLevel::top_down();
+        assert_eq!(levels.next(), Some(PageTableLevel::Four));
+        assert_eq!(levels.next(), Some(PageTableLevel::Three));
+        assert_eq!(levels.next(), Some(PageTableLevel::Two));
+        assert_eq!(levels.next(), Some(PageTableLevel::One));
+        assert_eq!(levels.next(), None);
+
+        #[allow(clippy::unusual_byte_groupings)]
+        let addr = VirtAddr::new(0o_001_002_003_004_0000);
+        assert_eq!(u16::from(PageTableLevel::Four.addr_index(addr)), 1);
+        assert_eq!(u16::from(PageTableLevel::Three.addr_index(addr)), 2);
+        assert_eq!(u16::from(PageTableLevel::Two.addr_index(addr)), 3);
+        assert_eq!(u16::from(PageTableLevel::One.addr_index(addr)), 4);
+    }
+
+    #[test]
+    fn cache_type_pat_layout() {
+        fn pat_index(flags: PageTableFlags, pat_bit: PageTableFlags) -> u64 {
+            u64::from(flags.contains(PageTableFlags::WRITE_THROUGH))
+                | u64::from(flags.contains(PageTableFlags::NO_CACHE)) << 1
+                | u64::from(flags.contains(pat_bit)) << 2
+        }
+
+        for (cache, expected) in [
+            (CacheType::WriteBack, 6),
+            (CacheType::WriteThrough, 4),
+            (CacheType::UncachedMinus, 7),
+            (CacheType::Uncacheable, 0),
+            (CacheType::WriteCombining, 1),
+        ] {
+            let memory_type = |index: u64| (CacheType::PAT_MSR_VALUE >> (index * 8)) & 0xff;
+            let index = u64::from(cache.pat_index());
+            assert_eq!(memory_type(index), expected);
+
+            let flags = cache.flags::<Size4KiB>();
+            assert_eq!(pat_index(flags, PageTableFlags::HUGE_PAGE), index);
+            assert!(!flags.contains(PageTableFlags::HUGE_PAGE_PAT));
+            for flags in [cache.flags::<Size2MiB>(), cache.flags::<Size1GiB>()] {
+                assert_eq!(pat_index(flags, PageTableFlags::HUGE_PAGE_PAT), index);
+                assert!(!flags.contains(PageTableFlags::HUGE_PAGE));
+            }
+        }
+
+        // Only the write-combining entry differs from the power-on default.
+        const DEFAULT_PAT: u64 = 0x0007_0406_0007_0406;
+        assert_eq!(CacheType::PAT_MSR_VALUE ^ DEFAULT_PAT, 0x05 << 40);
+    }
+
+    #[test]
+    fn pat_bits() {
+        let flags = PageTableFlags::PRESENT | PageTableFlags::WRITE_THROUGH;
+        let mut entry = PageTableEntry::new();
+
+        // Bit 12 is part of the address and bit 7 is the PAT bit in level 1 entries.
+        let frame = PhysFrame::containing_address(PhysAddr::new(0x3000));
+        entry.set_level_1_frame(frame, flags | PageTableFlags::HUGE_PAGE);
+        assert_eq!(entry.level_1_frame(), Ok(frame));
+        assert_eq!(entry.level_1_addr(), frame.start_address());
+        assert_eq!(entry.level_1_flags(), flags | PageTableFlags::HUGE_PAGE);
+        entry.set_level_1_flags(flags);
+        assert_eq!(entry.frame(), Ok(frame));
+        assert_eq!(entry.flags(), flags);
+
+        // Bit 12 is the PAT bit in huge page entries.
+        let frame = PhysFrame::<Size2MiB>::containing_address(PhysAddr::new(0x40_0000));
+        entry.set_huge_frame_2mib(frame, flags | PageTableFlags::HUGE_PAGE_PAT);
+        assert_eq!(entry.addr(), frame.start_address());
+        assert_eq!(
+            entry.flags(),
+            flags | PageTableFlags::HUGE_PAGE | PageTableFlags::HUGE_PAGE_PAT
+        );
+        entry.set_flags(flags | PageTableFlags::HUGE_PAGE);
+        assert_eq!(entry.addr(), frame.start_address());
+        assert_eq!(entry.flags(), flags | PageTableFlags::HUGE_PAGE);
+    }
+
+    #[test]
+    fn init_at() {
+        let mut table = core::mem::MaybeUninit::<PageTable>::uninit();
+        unsafe {
+            table.as_mut_ptr().cast::<u8>().write_bytes(0xff, 4096);
+            PageTable::init_at(table.as_mut_ptr());
+            assert!(table.assume_init_ref().is_empty());
+        }
+    }
+
+    #[test]
+    fn take_accessed_and_dirty() {
+        let mut entry = PageTableEntry::new();
+        let flags = PageTableFlags::PRESENT | PageTableFlags::ACCESSED | PageTableFlags::DIRTY;
+        entry.set_addr(PhysAddr::new(0x1000), flags);
+
+        assert!(entry.take_accessed());
+        assert!(!entry.take_accessed());
+        assert!(entry.flags().contains(PageTableFlags::DIRTY));
+
+        assert!(entry.take_dirty());
+        assert!(!entry.take_dirty());
+        assert_eq!(entry.flags(), PageTableFlags::PRESENT);
+        assert_eq!(entry.addr(), PhysAddr::new(0x1000));
+    }
+}
@@ -0,0 +1,16 @@
+//! Helpers for the paging tests.
+
+use crate::structures::paging::{FrameAllocator, PageTable, PhysFrame, Size4KiB};
+use crate::PhysAddr;
+
+/// Hands out the given page tables as frames, using their virtual address as physical
+/// address.
+pub(crate) struct TableAllocator<'a>(pub(crate) core::slice::IterMut<'a, PageTable>);
+
+unsafe impl FrameAllocator<Size4KiB> for TableAllocator<'_> {
+    fn allocate_frame(&mut self) -> Option<PhysFrame<Size4KiB>> {
+        let table = self.0.next()?;
+        let addr = PhysAddr::new(table as *mut PageTable as u64);
+        Some(PhysFrame::containing_address(addr))
+    }
+}
@@ -0,0 +1,161 @@
+//! Provides a standard register layout for `syscall` entry points.
+
+use crate::registers::rflags::RFlags;
+use crate::VirtAddr;
+
+/// The general purpose registers of a thread that entered the kernel through `syscall`.
+///
+/// Unlike interrupts, `syscall` doesn't switch stacks or push anything to the stack. It only
+/// stores the return address in `rcx` and the RFLAGS register in `r11`. The entry point must
+/// therefore switch to a kernel stack and save the registers itself. This type defines the layout
+/// in which the registers are saved, so that a Rust handler can take a `&mut SyscallRegisters`
+/// pointing to them. Saving and restoring the registers, as well as the stack switch, are still
+/// the responsibility of the (assembly) entry point.
+///
+/// The fields are ordered by increasing address, so an entry point that pushes the registers in
+/// reverse order (`r15` first, `rax` last) ends up with `rsp` pointing to a valid
+/// `SyscallRegisters`:
+///
+/// ```text
+/// push r15
+/// push r14
+/// ...
+/// push rbx
+/// push rax
+/// mov rdi, rsp        ; pass &mut SyscallRegisters as the first argument
+/// call syscall_handler
+/// ```
+///
+/// The user stack pointer is not part of this struct, as it must already be saved when the entry
+/// point switches to the kernel stack.
+///
+/// The system call number and arguments are interpreted according to the Linux convention by the
+/// helper methods: the number is passed in `rax`, the arguments in `rdi`, `rsi`, `rdx`, `r10`,
+/// `r8`, and `r9`, and the return value is passed back in `rax`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct SyscallRegisters {
+    /// The saved `rax` register.
+    pub rax: u64,
+    /// The saved `rbx` register.
+    pub rbx: u64,
+    /// Holds the user instruction pointer after `syscall`.
+    pub rcx: u64,
+    /// The saved `rdx` register.
+    pub rdx: u64,
+    /// The saved `rsi` register.
+    pub rsi: u64,
+    /// The saved `rdi` register.
+    pub rdi: u64,
+    /// The saved `rbp` register.
+    pub rbp: u64,
+    /// The saved `r8` register.
+    pub r8: u64,
+    /// The saved `r9` register.
+    pub r9: u64,
+    /// The saved `r10` register.
+    pub r10: u64,
+    /// Holds the user RFLAGS after `syscall`.
+    pub r11: u64,
+    /// The saved `r12` register.
+    pub r12: u64,
+    /// The saved `r13` register.
+    pub r13: u64,
+    /// The saved `r14` register.
+    pub r14: u64,
+    /// The saved `r15` register.
+    pub r15: u64,
+}
+
+impl SyscallRegisters {
+    /// Returns the system call number, which is passed in `rax`.
+    #[inline]
+    pub const fn number(&self) -> u64 {
+        self.rax
+    }
+
+    /// Returns the six system call arguments, which are passed in `rdi`, `rsi`, `rdx`, `r10`,
+    /// `r8`, and `r9`.
+    #[inline]
+    pub const fn args(&self) -> [u64; 6] {
+        [self.rdi, self.rsi, self.rdx, self.r10, self.r8, self.r9]
+    }
+
+    /// Sets the value returned to user space in `rax`.
+    #[inline]
+    pub fn set_return_value(&mut self, value: u64) {
+        self.rax = value;
+    }
+
+    /// Returns the address at which `sysret` continues, which is saved in `rcx`.
+    ///
+    /// The value is truncated if it is not canonical. Note that `sysret` raises a general
+    /// protection fault in ring 0 on Intel processors when returning to a non-canonical address.
+    #[inline]
+    pub const fn instruction_pointer(&self) -> VirtAddr {
+        VirtAddr::new_truncate(self.rcx)
+    }
+
+    /// Sets the address at which `sysret` continues.
+    #[inline]
+    pub fn set_instruction_pointer(&mut self, addr: VirtAddr) {
+        self.rcx = addr.as_u64();
+    }
+
+    /// Returns the RFLAGS value that `sysret` restores, which is saved in `r11`.
+    #[inline]
+    pub const fn rflags(&self) -> RFlags {
+        RFlags::from_bits_truncate(self.r11)
+    }
+
+    /// Sets the RFLAGS value that `sysret` restores.
+    #[inline]
+    pub fn set_rflags(&mut self, flags: RFlags) {
+        self.r11 = flags.bits();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::mem::size_of;
+
+    #[test]
+    fn layout() {
+        assert_eq!(size_of::<SyscallRegisters>(), 15 * 8);
+
+        let regs = SyscallRegisters::default();
+        let base = &regs as *const _ as usize;
+        assert_eq!(&regs.rax as *const _ as usize - base, 0);
+        assert_eq!(&regs.rcx as *const _ as usize - base, 2 * 8);
+        assert_eq!(&regs.r11 as *const _ as usize - base, 10 * 8);
+        assert_eq!(&regs.r15 as *const _ as usize - base, 14 * 8);
+    }
+
+    #[test]
+    fn accessors() {
+        let mut regs = SyscallRegisters {
+            rax: 60,
+            rdi: 1,
+            rsi: 2,
+            rdx: 3,
+            r10: 4,
+            r8: 5,
+            r9: 6,
+            rcx: 0x40_1000,
+            r11: 0x202,
+            ..Default::default()
+        };
+        assert_eq!(regs.number(), 60);
+        assert_eq!(regs.args(), [1, 2, 3, 4, 5, 6]);
+        assert_eq!(regs.instruction_pointer(), VirtAddr::new(0x40_1000));
+        assert_eq!(regs.rflags(), RFlags::INTERRUPT_FLAG);
+
+        regs.set_return_value(42);
+        regs.set_instruction_pointer(VirtAddr::new(0x40_2000));
+        regs.set_rflags(RFlags::INTERRUPT_FLAG);
+        assert_eq!(regs.rax, 42);
+        assert_eq!(regs.rcx, 0x40_2000);
+        assert_eq!(regs.r11, 0x200);
+    }
+}
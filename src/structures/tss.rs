@@ -30,6 +30,15 @@ impl TaskStateSegment {
     /// As we always set the TSS segment limit to
     /// `size_of::<TaskStateSegment>() - 1`, this means that `iomap_base` is
     /// initialized to `size_of::<TaskStateSegment>()`.
+    ///
+    /// ## I/O permission bitmap
+    ///
+    /// To use an I/O permission bitmap, place the TSS at the start of a `#[repr(C)]` struct
+    /// followed by the bitmap and a final `0xff` byte, and set `iomap_base` to the offset of the
+    /// bitmap within that struct. The TSS descriptor must then cover the bitmap too, so it has to
+    /// be created through
+    /// [`Descriptor::tss_segment_with_iomap`](crate::structures::gdt::Descriptor::tss_segment_with_iomap)
+    /// instead of [`Descriptor::tss_segment`](crate::structures::gdt::Descriptor::tss_segment).
     #[inline]
     pub const fn new() -> TaskStateSegment {
         TaskStateSegment {
@@ -42,6 +51,21 @@ impl TaskStateSegment {
             reserved_4: 0,
         }
     }
+
+    /// Returns the segment limit that a TSS descriptor for this TSS needs, given an I/O
+    /// permission bitmap of `iomap_size` bytes at [`iomap_base`](Self::iomap_base).
+    ///
+    /// The size must not include the `0xff` byte that has to follow the bitmap; the returned
+    /// limit covers that byte too. If `iomap_size` is zero, the limit only covers the TSS itself.
+    #[inline]
+    pub const fn descriptor_limit(&self, iomap_size: u16) -> u32 {
+        if iomap_size == 0 {
+            size_of::<TaskStateSegment>() as u32 - 1
+        } else {
+            // The limit is inclusive, so this covers the byte following the bitmap.
+            self.iomap_base as u32 + iomap_size as u32
+        }
+    }
 }
 
 impl Default for TaskStateSegment {
@@ -61,4 +85,25 @@ mod tests {
         // minimum limit of 0x67.
         assert_eq!(size_of::<TaskStateSegment>(), 0x68);
     }
+
+    #[test]
+    pub fn check_new_defaults() {
+        const TSS: TaskStateSegment = TaskStateSegment::new();
+        let iomap_base = TSS.iomap_base;
+        assert_eq!(usize::from(iomap_base), size_of::<TaskStateSegment>());
+        let interrupt_stack_table = TSS.interrupt_stack_table;
+        assert!(interrupt_stack_table.iter().all(|addr| addr.is_null()));
+        let privilege_stack_table = TSS.privilege_stack_table;
+        assert!(privilege_stack_table.iter().all(|addr| addr.is_null()));
+    }
+
+    #[test]
+    pub fn check_descriptor_limit() {
+        let mut tss = TaskStateSegment::new();
+        assert_eq!(tss.descriptor_limit(0), 0x67);
+        assert_eq!(tss.descriptor_limit(8192), 0x68 + 8192);
+
+        tss.iomap_base = 0x70;
+        assert_eq!(tss.descriptor_limit(1), 0x71);
+    }
 }
@@ -0,0 +1,71 @@
+#![no_std]
+#![no_main]
+
+use core::arch::x86_64::_rdtsc;
+use core::panic::PanicInfo;
+use testing::{exit_qemu, serial_print, serial_println, QemuExitCode};
+use x86_64::instructions::port::{Port, PortWriteOnly};
+
+// These ports select and read a register in the PCI configuration space
+const PCI_CONFIG_ADDRESS_PORT: u16 = 0xCF8;
+const PCI_CONFIG_DATA_PORT: u16 = 0xCFC;
+
+// Selects the vendor and device ID register of the host bridge, which can be read
+// repeatedly without side effects
+const HOST_BRIDGE_ID_REGISTER: u32 = 0x8000_0000;
+
+const BUFFER_LEN: usize = 4096;
+
+/// This function is the entry point, since the linker looks for a function
+/// named `_start_` by default.
+#[no_mangle] // don't mangle the name of this function
+pub extern "C" fn _start() -> ! {
+    serial_print!("port_read_buffer... ");
+
+    let mut address_port = PortWriteOnly::<u32>::new(PCI_CONFIG_ADDRESS_PORT);
+    let mut data_port = Port::<u32>::new(PCI_CONFIG_DATA_PORT);
+    let mut buffer = [0; BUFFER_LEN];
+    let mut loop_buffer = [0; BUFFER_LEN];
+
+    let (buffer_cycles, loop_cycles) = unsafe {
+        address_port.write(HOST_BRIDGE_ID_REGISTER);
+        let expected = data_port.read();
+
+        let start = _rdtsc();
+        let count = data_port.read_buffer(&mut buffer);
+        let buffer_cycles = _rdtsc() - start;
+
+        let start = _rdtsc();
+        for value in loop_buffer.iter_mut() {
+            *value = data_port.read();
+        }
+        let loop_cycles = _rdtsc() - start;
+
+        if count != BUFFER_LEN {
+            panic!("read_buffer returned {} instead of {}", count, BUFFER_LEN);
+        }
+        if let Some(value) = buffer.iter().find(|&&value| value != expected) {
+            panic!("read_buffer: {:#x} does not match {:#x}", value, expected);
+        }
+        if loop_buffer != buffer {
+            panic!("read loop does not match read_buffer");
+        }
+        (buffer_cycles, loop_cycles)
+    };
+
+    serial_println!("[ok]");
+    serial_println!(
+        "    {} reads: read_buffer took {} cycles, read loop took {} cycles",
+        BUFFER_LEN,
+        buffer_cycles,
+        loop_cycles
+    );
+    exit_qemu(QemuExitCode::Success);
+
+    loop {}
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    testing::test_panic_handler(info)
+}